@@ -0,0 +1,441 @@
+//! Procedural macros for [`anon_iter`](https://docs.rs/anon_iter), enabled by its `macros` feature.
+//!
+//! Use them through `anon_iter::macros` rather than depending on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::visit_mut::{self, VisitMut};
+use syn::{
+    parse_macro_input, Block, Data, DeriveInput, Expr, ExprMatch, Fields, GenericParam, Ident,
+    Item, ItemEnum, ItemFn, Stmt, Token,
+};
+
+/// The number of variants of each enum `anon_iter` provides, with all of its arity features
+const ARITIES: &[usize] = &[2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 16, 24, 32];
+
+/// The largest enum `anon_iter` provides, with the `high-arity` feature
+const MAX_VARIANTS: usize = 32;
+
+/// The traits that `anon_iter::__forward!` forwards to the variants of an enum which need
+/// every variant to yield the same items
+const ITERATOR_TRAITS: &[&str] = &[
+    "Iterator",
+    "DoubleEndedIterator",
+    "ExactSizeIterator",
+    "FusedIterator",
+];
+
+/// The other traits that `anon_iter::__forward!` forwards. `Future` also needs every variant
+/// to have the same `Output`, and `Read`, `BufRead` and `Write` need `std`.
+const OTHER_TRAITS: &[&str] = &[
+    "Display", "Binary", "Octal", "LowerHex", "UpperHex", "LowerExp", "UpperExp", "Error",
+    "Future", "Read", "BufRead", "Write",
+];
+
+/// On a function, wraps every value it returns in a variant of `anon_iter::AnonIterN`.
+/// On an enum, forwards the listed traits to its variants.
+///
+/// See `anon_iter::macros::anon` for the documentation.
+#[proc_macro_attribute]
+pub fn anon(args: TokenStream, item: TokenStream) -> TokenStream {
+    let result = match parse_macro_input!(item as Item) {
+        Item::Fn(function) => wrap_returns(args.into(), function),
+        Item::Enum(item) => forward_traits(args.into(), item),
+        item => Err(syn::Error::new(
+            item.span(),
+            "`#[anon]` only goes on functions and enums",
+        )),
+    };
+    result.unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+fn wrap_returns(args: TokenStream2, mut function: ItemFn) -> syn::Result<TokenStream2> {
+    if !args.is_empty() {
+        return Err(syn::Error::new(
+            args.span(),
+            "`#[anon]` takes no arguments on functions",
+        ));
+    }
+
+    let mut count = 0;
+    for_each_return_point(&mut function.block, &mut |_| count += 1);
+    if count > MAX_VARIANTS {
+        return Err(syn::Error::new(
+            function.sig.ident.span(),
+            format!(
+                "this function returns from {} places, but `#[anon]` supports at most {}",
+                count, MAX_VARIANTS
+            ),
+        ));
+    }
+    if count >= 2 {
+        // Above 12, the values go in the smallest of `AnonIter16`, `AnonIter24` and `AnonIter32`
+        // that fits them, and the variants left over hold `Empty`
+        let arity = ARITIES
+            .iter()
+            .copied()
+            .find(|&arity| arity >= count)
+            .unwrap_or(MAX_VARIANTS);
+        let anon_iter = format_ident!("AnonIter{}", arity);
+        let params = (0..arity).map(|index| {
+            if index < count {
+                quote!(_)
+            } else {
+                quote!(::core::iter::Empty<_>)
+            }
+        });
+        let ty = if arity == count {
+            quote!(::anon_iter::#anon_iter)
+        } else {
+            quote!(::anon_iter::#anon_iter::<#(#params),*>)
+        };
+        let mut index = 0usize;
+        for_each_return_point(&mut function.block, &mut |expr| {
+            index += 1;
+            let variant = format_ident!("I{}", index);
+            let span = expr.span();
+            let inner = std::mem::replace(expr, Expr::Verbatim(Default::default()));
+            *expr = Expr::Verbatim(quote_spanned! {span=>
+                #ty::#variant(#inner)
+            });
+        });
+        // Fails with the name of the feature that enables the enum, when it's disabled
+        let block = &function.block;
+        function.block = Box::new(syn::parse_quote!({
+            ::anon_iter::__anon_arity!(#anon_iter #block)
+        }));
+    }
+
+    Ok(function.into_token_stream())
+}
+
+fn forward_traits(args: TokenStream2, item: ItemEnum) -> syn::Result<TokenStream2> {
+    let traits = Punctuated::<Ident, Token![,]>::parse_terminated.parse2(args)?;
+    if traits.is_empty() {
+        return Err(syn::Error::new(
+            item.ident.span(),
+            "list the traits to forward to the variants, like `#[anon(Iterator, Display)]`",
+        ));
+    }
+    for name in &traits {
+        if !ITERATOR_TRAITS
+            .iter()
+            .chain(OTHER_TRAITS)
+            .any(|known| name == known)
+        {
+            return Err(syn::Error::new(
+                name.span(),
+                format!(
+                    "`{}` can't be forwarded, the traits that can are {}",
+                    name,
+                    ITERATOR_TRAITS
+                        .iter()
+                        .chain(OTHER_TRAITS)
+                        .copied()
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            ));
+        }
+    }
+
+    let forward = Forward::new(&DeriveInput::from(item.clone()))?;
+    let impls = traits.iter().map(|name| forward.family(name));
+    Ok(quote!(#item #(#impls)*))
+}
+
+/// Forwards `Iterator`, `DoubleEndedIterator`, `ExactSizeIterator` and `FusedIterator`
+/// to the active variant of an enum
+///
+/// See `anon_iter::macros::IteratorEnum` for the documentation.
+#[proc_macro_derive(IteratorEnum)]
+pub fn derive_iterator_enum(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match Forward::new(&input) {
+        Ok(forward) => ITERATOR_TRAITS
+            .iter()
+            .map(|name| forward.family(&format_ident!("{}", name)))
+            .collect::<TokenStream2>()
+            .into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Wraps each arm of a `match` in a variant of an enum defined just for it
+///
+/// See `anon_iter::macros::match_iters` for the documentation.
+#[proc_macro]
+pub fn match_iters(input: TokenStream) -> TokenStream {
+    let mut expr = parse_macro_input!(input as ExprMatch);
+
+    let mut variants = Vec::new();
+    for arm in &mut expr.arms {
+        if never_returns(&arm.body) {
+            continue;
+        }
+        let variant = format_ident!("I{}", variants.len() + 1);
+        let param = format_ident!("__I{}", variants.len() + 1);
+        let body = &arm.body;
+        let wrapped = quote_spanned! {body.span()=>
+            __MatchIters::#variant(#body)
+        };
+        *arm.body = Expr::Verbatim(wrapped);
+        arm.comma.get_or_insert_with(Default::default);
+        variants.push((variant, param));
+    }
+    if variants.is_empty() {
+        return expr.into_token_stream().into();
+    }
+
+    let params = variants.iter().map(|(_, param)| param);
+    let definition = variants
+        .iter()
+        .map(|(variant, param)| quote!(#variant(#param)));
+    let item = quote! {
+        enum __MatchIters<#(#params),*> {
+            #(#definition),*
+        }
+    };
+    let impls =
+        match syn::parse2::<DeriveInput>(item.clone()).and_then(|input| Forward::new(&input)) {
+            Ok(forward) => ITERATOR_TRAITS
+                .iter()
+                .map(|name| forward.family(&format_ident!("{}", name)))
+                .collect::<TokenStream2>(),
+            Err(err) => err.to_compile_error(),
+        };
+
+    quote!({
+        #item
+        #impls
+        #expr
+    })
+    .into()
+}
+
+/// The parts of an enum that `anon_iter::__forward!` needs to forward a family of traits
+struct Forward {
+    /// The generic parameters of the enum, without their defaults
+    params: TokenStream2,
+    /// The enum, with its generic parameters applied
+    ty: TokenStream2,
+    /// The enum's own `where` predicates
+    own: TokenStream2,
+    /// The type of the variant that the associated types, such as `Item`, are taken from
+    first: syn::Type,
+    /// The types of the other variants
+    rest: Vec<syn::Type>,
+    /// The variants and the types they wrap
+    variants: Vec<(syn::Ident, syn::Type)>,
+}
+
+impl Forward {
+    fn new(input: &DeriveInput) -> syn::Result<Self> {
+        let data = match &input.data {
+            Data::Enum(data) => data,
+            _ => {
+                return Err(syn::Error::new(
+                    input.ident.span(),
+                    "only enums can forward to their variants",
+                ))
+            }
+        };
+        let variants = data
+            .variants
+            .iter()
+            .map(|variant| match &variant.fields {
+                Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                    Ok((variant.ident.clone(), fields.unnamed[0].ty.clone()))
+                }
+                _ => Err(syn::Error::new(
+                    variant.span(),
+                    "every variant must wrap exactly one value, like `Variant(Inner)`",
+                )),
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+        if variants.is_empty() {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "an enum without variants has nothing to forward to",
+            ));
+        }
+        // The item type is taken from a variant that depends on a type parameter if there
+        // is one, because bounds like `I: Iterator<Item = <Concrete as Iterator>::Item>` can't
+        // be proven when the enum already bounds `I: Iterator<Item = u32>`
+        let type_params = input
+            .generics
+            .type_params()
+            .map(|param| &param.ident)
+            .collect::<Vec<_>>();
+        let source = variants
+            .iter()
+            .position(|(_, inner)| mentions(inner.to_token_stream(), &type_params))
+            .unwrap_or(0);
+        let first = &variants[source].1;
+
+        let mut generics = input.generics.clone();
+        for param in &mut generics.params {
+            match param {
+                GenericParam::Type(param) => param.default = None,
+                GenericParam::Const(param) => param.default = None,
+                GenericParam::Lifetime(_) => {}
+            }
+        }
+        let params = generics.params.to_token_stream();
+        let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+        let name = &input.ident;
+
+        let own = where_clause.map(|clause| {
+            let predicates = clause.predicates.iter();
+            quote!(#(#predicates,)*)
+        });
+        // A variant of the same type as `first` has its items already, and bounding it with
+        // `Inner: Iterator<Item = <Inner as Iterator>::Item>` would overflow
+        let first_tokens = first.to_token_stream().to_string();
+        let rest = variants
+            .iter()
+            .filter(|(_, inner)| inner.to_token_stream().to_string() != first_tokens)
+            .map(|(_, inner)| inner.clone())
+            .collect();
+
+        Ok(Self {
+            params,
+            ty: quote!(#name #ty_generics),
+            own: quote!(#own),
+            first: first.clone(),
+            rest,
+            variants,
+        })
+    }
+
+    /// Forwards the traits of `family`, one of `ITERATOR_TRAITS` or `OTHER_TRAITS`, to the variants
+    fn family(&self, family: &Ident) -> TokenStream2 {
+        let Self {
+            params,
+            ty,
+            own,
+            first,
+            rest,
+            variants,
+        } = self;
+        let inners = variants.iter().map(|(_, inner)| inner);
+        let variants = variants
+            .iter()
+            .map(|(variant, inner)| quote!(#variant: #inner));
+        if ITERATOR_TRAITS.iter().any(|name| family == name) {
+            let item = quote!(<#first as ::core::iter::Iterator>::Item);
+            quote! {
+                ::anon_iter::__forward!(
+                    #family [] [#params] #ty,
+                    [#own #(#rest: ::core::iter::Iterator<Item = #item>,)*], #item, #(#variants),*
+                );
+            }
+        } else if family == "Future" {
+            let output = quote!(<#first as ::core::future::Future>::Output);
+            quote! {
+                ::anon_iter::__forward!(
+                    #family [] [#params] #ty,
+                    [#own #(#rest: ::core::future::Future<Output = #output>,)*], #output, #(#variants),*
+                );
+                ::anon_iter::__forward!(@pinned [#params] #ty, [#own], #(#inners),*);
+            }
+        } else {
+            quote! {
+                ::anon_iter::__forward!(#family [] [#params] #ty, [#own], (), #(#variants),*);
+            }
+        }
+    }
+}
+
+/// Whether `tokens` contain any of `idents`
+fn mentions(tokens: TokenStream2, idents: &[&syn::Ident]) -> bool {
+    tokens.into_iter().any(|token| match token {
+        proc_macro2::TokenTree::Ident(ident) => idents.iter().any(|param| **param == ident),
+        proc_macro2::TokenTree::Group(group) => mentions(group.stream(), idents),
+        _ => false,
+    })
+}
+
+/// Calls `f` with every expression whose value `block` returns, in source order
+///
+/// These are the operands of `return` and the tail expression. Tail expressions that are
+/// `match`, `if` or block expressions are looked into, so each of their branches is reported
+/// instead of the whole expression.
+fn for_each_return_point(block: &mut Block, f: &mut dyn FnMut(&mut Expr)) {
+    let mut returns = Returns { f };
+    returns.visit_block_mut(block);
+    if let Some(Stmt::Expr(tail, None)) = block.stmts.last_mut() {
+        returns.tail(tail);
+    }
+}
+
+/// Finds the `return`s of a function, ignoring those of nested closures, `async` blocks and items
+struct Returns<'f> {
+    f: &'f mut dyn FnMut(&mut Expr),
+}
+
+impl Returns<'_> {
+    /// Reports the branches of `expr`, which is returned from the function
+    fn tail(&mut self, expr: &mut Expr) {
+        match expr {
+            Expr::Match(expr) => {
+                for arm in &mut expr.arms {
+                    self.tail(&mut arm.body);
+                }
+            }
+            Expr::If(expr) if expr.else_branch.is_some() => {
+                self.block_tail(&mut expr.then_branch);
+                if let Some((_, else_branch)) = &mut expr.else_branch {
+                    self.tail(else_branch);
+                }
+            }
+            Expr::Block(block) if block.label.is_none() => self.block_tail(&mut block.block),
+            Expr::Paren(expr) => self.tail(&mut expr.expr),
+            expr if never_returns(expr) => {}
+            _ => (self.f)(expr),
+        }
+    }
+
+    fn block_tail(&mut self, block: &mut Block) {
+        if let Some(Stmt::Expr(tail, None)) = block.stmts.last_mut() {
+            self.tail(tail);
+        }
+    }
+}
+
+impl VisitMut for Returns<'_> {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        match expr {
+            Expr::Closure(_) | Expr::Async(_) => {}
+            Expr::Return(ret) => {
+                if let Some(value) = &mut ret.expr {
+                    self.visit_expr_mut(value);
+                    self.tail(value);
+                }
+            }
+            _ => visit_mut::visit_expr_mut(self, expr),
+        }
+    }
+
+    fn visit_item_mut(&mut self, _: &mut syn::Item) {}
+}
+
+/// Whether `expr` never produces a value, so there is nothing to wrap
+///
+/// These are `return`, `break`, `continue` and the standard macros that never return.
+fn never_returns(expr: &Expr) -> bool {
+    match expr {
+        Expr::Return(_) | Expr::Break(_) | Expr::Continue(_) => true,
+        Expr::Macro(expr) => matches!(
+            expr.mac.path.get_ident(),
+            Some(ident) if ["panic", "unreachable", "todo", "unimplemented"]
+                .iter()
+                .any(|name| ident == name)
+        ),
+        _ => false,
+    }
+}
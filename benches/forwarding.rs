@@ -0,0 +1,116 @@
+//! Compares iterating through an `AnonIterN` against the two usual alternatives:
+//! nested `Either`s and `Box<dyn Iterator>`.
+//!
+//! Run with `cargo bench`.
+
+use anon_iter::AnonIter4;
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use either::Either;
+use std::iter::Chain;
+use std::ops::Range;
+
+const LEN: u64 = 10_000;
+
+type Chained = Chain<Range<u64>, Range<u64>>;
+type Anon = AnonIter4<Range<u64>, Chained, std::vec::IntoIter<u64>, std::iter::Empty<u64>>;
+type Nested =
+    Either<Range<u64>, Either<Chained, Either<std::vec::IntoIter<u64>, std::iter::Empty<u64>>>>;
+type Boxed = Box<dyn Iterator<Item = u64>>;
+
+/// The iterators each branch returns. `Chain` is the interesting one: its
+/// `fold` is much faster than a loop over its `next`.
+fn chained() -> Chained {
+    (0..LEN / 2).chain(LEN / 2..LEN)
+}
+
+fn anon(branch: u8) -> Anon {
+    match branch {
+        0 => AnonIter4::I1(0..LEN),
+        1 => AnonIter4::I2(chained()),
+        2 => AnonIter4::I3((0..LEN).collect::<Vec<_>>().into_iter()),
+        _ => AnonIter4::I4(std::iter::empty()),
+    }
+}
+
+fn nested(branch: u8) -> Nested {
+    match branch {
+        0 => Either::Left(0..LEN),
+        1 => Either::Right(Either::Left(chained())),
+        2 => Either::Right(Either::Right(Either::Left(
+            (0..LEN).collect::<Vec<_>>().into_iter(),
+        ))),
+        _ => Either::Right(Either::Right(Either::Right(std::iter::empty()))),
+    }
+}
+
+fn boxed(branch: u8) -> Boxed {
+    match branch {
+        0 => Box::new(0..LEN),
+        1 => Box::new(chained()),
+        2 => Box::new((0..LEN).collect::<Vec<_>>().into_iter()),
+        _ => Box::new(std::iter::empty()),
+    }
+}
+
+/// A `for` loop calls `next` for every item
+fn next_loop(iter: impl Iterator<Item = u64>) -> u64 {
+    let mut sum = 0;
+    for x in iter {
+        sum += x;
+    }
+    sum
+}
+
+fn fold(iter: impl Iterator<Item = u64>) -> u64 {
+    iter.fold(0, |acc, x| acc ^ x.wrapping_mul(31))
+}
+
+fn collect(iter: impl Iterator<Item = u64>) -> Vec<u64> {
+    iter.collect()
+}
+
+fn sum(iter: impl Iterator<Item = u64>) -> u64 {
+    iter.sum()
+}
+
+macro_rules! bench_all {
+    ($c:ident, $method:ident) => {
+        let mut group = $c.benchmark_group(stringify!($method));
+        for (branch, inner) in ["Range", "Chain", "vec::IntoIter"].iter().enumerate() {
+            let branch = branch as u8;
+            let name = |wrapper| format!("{}/{}", inner, wrapper);
+            group.bench_function(name("AnonIter4"), |b| {
+                b.iter_batched(
+                    || anon(branch),
+                    |it| black_box($method(it)),
+                    BatchSize::SmallInput,
+                )
+            });
+            group.bench_function(name("Either"), |b| {
+                b.iter_batched(
+                    || nested(branch),
+                    |it| black_box($method(it)),
+                    BatchSize::SmallInput,
+                )
+            });
+            group.bench_function(name("Box<dyn Iterator>"), |b| {
+                b.iter_batched(
+                    || boxed(branch),
+                    |it| black_box($method(it)),
+                    BatchSize::SmallInput,
+                )
+            });
+        }
+        group.finish();
+    };
+}
+
+fn forwarding(c: &mut Criterion) {
+    bench_all!(c, next_loop);
+    bench_all!(c, fold);
+    bench_all!(c, collect);
+    bench_all!(c, sum);
+}
+
+criterion_group!(benches, forwarding);
+criterion_main!(benches);
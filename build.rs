@@ -0,0 +1,48 @@
+//! Detects the version of the compiler, to forward the methods that newer
+//! versions stabilized without raising the MSRV for everyone
+
+use std::env;
+use std::process::Command;
+
+/// The minor version of `rustc`, e.g. `82` for Rust 1.82
+fn rustc_minor_version() -> Option<u32> {
+    let rustc = env::var_os("RUSTC")?;
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    let version = String::from_utf8(output.stdout).ok()?;
+    // "rustc 1.82.0 (f6e511eec 2024-10-15)"
+    version.split('.').nth(1)?.parse().ok()
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rustc-check-cfg=cfg(anon_iter_const_panic)");
+    println!("cargo:rustc-check-cfg=cfg(anon_iter_control_flow)");
+    println!("cargo:rustc-check-cfg=cfg(anon_iter_core_error)");
+    println!("cargo:rustc-check-cfg=cfg(anon_iter_core_unwind_safe)");
+    println!("cargo:rustc-check-cfg=cfg(anon_iter_is_sorted)");
+
+    let minor = match rustc_minor_version() {
+        Some(minor) => minor,
+        None => return,
+    };
+    // `core::ops::ControlFlow`
+    if minor >= 55 {
+        println!("cargo:rustc-cfg=anon_iter_control_flow");
+    }
+    // `core::panic::UnwindSafe` and `RefUnwindSafe`
+    if minor >= 56 {
+        println!("cargo:rustc-cfg=anon_iter_core_unwind_safe");
+    }
+    // `assert!` in constants
+    if minor >= 57 {
+        println!("cargo:rustc-cfg=anon_iter_const_panic");
+    }
+    // `core::error::Error`
+    if minor >= 81 {
+        println!("cargo:rustc-cfg=anon_iter_core_error");
+    }
+    // `Iterator::is_sorted`, `is_sorted_by` and `is_sorted_by_key`
+    if minor >= 82 {
+        println!("cargo:rustc-cfg=anon_iter_is_sorted");
+    }
+}
@@ -0,0 +1,170 @@
+//! Measures how long it takes to compile the same code written with `anon_iter`
+//! and with [`auto_enums`](https://crates.io/crates/auto_enums).
+//!
+//! Both crates get the same `FUNCTIONS` functions (default: 200), each returning
+//! one of 4 different iterators from a `match`. Each crate is built twice:
+//!
+//! - from scratch, which includes compiling its dependencies
+//! - after touching its `lib.rs`, which only recompiles the crate itself
+//!
+//! ```text
+//! cargo run --release --manifest-path compile-bench/Cargo.toml -- [FUNCTIONS]
+//! ```
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+/// A way of returning different iterators from a function
+struct Approach {
+    name: &'static str,
+    dependency: String,
+    /// Creates the body of the `n`th function
+    function: fn(usize) -> String,
+}
+
+/// The 4 branches that every generated function returns from. The closure
+/// makes each function's iterator types unique, like they would be in real code.
+fn branches(n: usize) -> [String; 4] {
+    [
+        "0..x".to_string(),
+        "core::iter::once(x)".to_string(),
+        format!("(0..x).map(move |y| y * {})", n + 1),
+        "core::iter::empty()".to_string(),
+    ]
+}
+
+fn anon_iter(n: usize) -> String {
+    let [i1, i2, i3, i4] = branches(n);
+    format!(
+        "pub fn f{n}(x: u32) -> impl Iterator<Item = u32> {{
+    match x % 4 {{
+        0 => anon_iter::AnonIter4::I1({i1}),
+        1 => anon_iter::AnonIter4::I2({i2}),
+        2 => anon_iter::AnonIter4::I3({i3}),
+        _ => anon_iter::AnonIter4::I4({i4}),
+    }}
+}}
+",
+        n = n,
+        i1 = i1,
+        i2 = i2,
+        i3 = i3,
+        i4 = i4,
+    )
+}
+
+fn auto_enums(n: usize) -> String {
+    let [i1, i2, i3, i4] = branches(n);
+    format!(
+        "#[auto_enums::auto_enum(Iterator)]
+pub fn f{n}(x: u32) -> impl Iterator<Item = u32> {{
+    match x % 4 {{
+        0 => {i1},
+        1 => {i2},
+        2 => {i3},
+        _ => {i4},
+    }}
+}}
+",
+        n = n,
+        i1 = i1,
+        i2 = i2,
+        i3 = i3,
+        i4 = i4,
+    )
+}
+
+/// Writes a crate containing `functions` functions generated by `approach`
+fn generate(dir: &Path, approach: &Approach, functions: usize) {
+    let mut lib = String::new();
+    for n in 0..functions {
+        lib.push_str(&(approach.function)(n));
+    }
+    let _ = write!(lib, "\npub fn sum(x: u32) -> u32 {{\n    0");
+    for n in 0..functions {
+        let _ = write!(lib, " + f{}(x).sum::<u32>()", n);
+    }
+    lib.push_str("\n}\n");
+
+    let manifest = format!(
+        "[package]
+name = \"bench-{name}\"
+version = \"0.0.0\"
+edition = \"2018\"
+publish = false
+
+[workspace]
+
+[dependencies]
+{dependency}
+",
+        name = approach.name.replace('_', "-"),
+        dependency = approach.dependency,
+    );
+
+    fs::create_dir_all(dir.join("src")).expect("create the generated crate");
+    fs::write(dir.join("Cargo.toml"), manifest).expect("write Cargo.toml");
+    fs::write(dir.join("src/lib.rs"), lib).expect("write lib.rs");
+}
+
+/// Times `cargo build` in `dir`
+fn build(dir: &Path) -> Duration {
+    let start = Instant::now();
+    let status = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()))
+        .arg("build")
+        .arg("--quiet")
+        .current_dir(dir)
+        .status()
+        .expect("run cargo");
+    let elapsed = start.elapsed();
+    assert!(status.success(), "building {} failed", dir.display());
+    elapsed
+}
+
+fn main() {
+    let functions = env::args()
+        .nth(1)
+        .map(|n| n.parse().expect("FUNCTIONS must be a number"))
+        .unwrap_or(200);
+
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let anon_iter_path = root
+        .parent()
+        .expect("compile-bench lives in the repository");
+    let approaches = [
+        Approach {
+            name: "anon_iter",
+            dependency: format!("anon_iter = {{ path = {:?} }}", anon_iter_path),
+            function: anon_iter,
+        },
+        Approach {
+            name: "auto_enums",
+            dependency: "auto_enums = \"0.8\"".to_string(),
+            function: auto_enums,
+        },
+    ];
+
+    println!("{} functions returning 1 of 4 iterators\n", functions);
+    println!("{:<12} {:>12} {:>12}", "", "clean", "incremental");
+    for approach in &approaches {
+        let dir = root.join("target/generated").join(approach.name);
+        let _ = fs::remove_dir_all(&dir);
+        generate(&dir, approach, functions);
+
+        let clean = build(&dir);
+        // Only the generated crate is rebuilt after its source changes
+        let lib = dir.join("src/lib.rs");
+        fs::write(&lib, fs::read_to_string(&lib).expect("read lib.rs") + "\n")
+            .expect("touch lib.rs");
+        let incremental = build(&dir);
+
+        println!(
+            "{:<12} {:>12.2?} {:>12.2?}",
+            approach.name, clean, incremental
+        );
+    }
+}
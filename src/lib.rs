@@ -28,6 +28,24 @@
 //! }
 //! ```
 //!
+//! The [`IteratorExt`] trait does the same wrapping as a method, e.g. `(1..10).anon_i1_of_2()`,
+//! and so do the functions in the `ofN` modules, e.g. [`of2::i1(1..10)`](of2::i1).
+//! [`prelude`] imports the enums, [`IteratorExt`] and the short aliases [`A1`] to `A12`.
+//! The [`anon!`] macro wraps every arm of a `match` in the right variant, counting the arms for you.
+//! For more than 12 iterators, or fewer traits, [`define_anon_iter!`] defines an enum of your own.
+//! Code generators that need any number of iterators can nest [`AnonChain`] instead.
+//! To erase the types of the iterators, `AnonIterDyn` boxes one (with the `alloc` feature),
+//! [`AnonIterRef`] borrows one and [`AnonIterInline`] stores one in a buffer of fixed size.
+//!
+//! The enums aren't limited to iterators: [`Anon2`] to `Anon12` are general sum types, implementing
+//! traits such as `Display`, `Future` and `Error` whenever all of their variants do.
+//! `AnonIter2` to `AnonIter12` are other names for the same enums.
+//! When the variants yield different items, [`AnonIterInto`] converts them into a common type,
+//! and [`AnonIterHetero`] wraps each of them in the variant it came from.
+//! [`AnonTryIter`] does the same for the errors of fallible variants.
+//! Branches can hold collections such as a `Vec` and call `into_iters` once, instead of `into_iter` in each.
+//! [`AnonIterLite`] forwards only `next` and `next_back`, and leaves the other methods to their defaults.
+//!
 //! The crate [`Either`](https://docs.rs/either/latest/either/) allows similar functionality, as it too implements `Iterator` when
 //! its type parameters are both `Iterator`.
 //!
@@ -36,82 +54,3356 @@
 //!
 //! Additionally, `anon_iter` makes code more readable because it may not be instantly obvious that we are using `Either` for this purpose, but with `AnonEnum`
 //! the intent is apparent.
+//!
+//! # Cargo features
+//!
+//! - `nightly`: also forwards the methods whose overrides need unstable traits, such as
+//!   [`Iterator::try_fold`] (which needs `Try`), and nightly-only methods such as `advance_by`.
+//! - `alloc`: adds `boxed` and `boxed_local`, which erase the type of an enum by boxing it
+//!   into a `Box<dyn Iterator>`, and `AnonIterDyn`, a boxed iterator every enum converts into.
+//! - `arbitrary`: implements [`arbitrary::Arbitrary`](https://docs.rs/arbitrary) for the enums,
+//!   for fuzzing.
+//! - `arity-4`, `arity-8` and `arity-12`: emit the enums with up to 4, 8 and 12 variants.
+//!   `AnonIter1` to `AnonIter3` are always there. `arity-12` is on by default, and turning it
+//!   off with `default-features = false` cuts compile time when only the small enums are needed.
+//! - `defmt`: implements [`defmt::Format`](https://docs.rs/defmt) for the enums,
+//!   printing the variant name around the wrapped iterator.
+//! - `double-ended`, `exact-size`, `fmt` and `fused`: forward `DoubleEndedIterator`, `ExactSizeIterator`,
+//!   the formatting traits such as `Display`, and `FusedIterator`. They are on by default, and turning
+//!   them off shaves compile time in workspaces that instantiate the enums with many different types.
+//! - `either`: converts [`AnonIter2`] from and into [`either::Either`](https://docs.rs/either),
+//!   and flattens nested `Either`s with `AnonIter3::from_nested` and `AnonIter4::from_nested`.
+//! - `futures`: converts [`AnonIter2`] from and into
+//!   [`futures::future::Either`](https://docs.rs/futures/latest/futures/future/enum.Either.html).
+//! - `high-arity`: adds `AnonIter16`, `AnonIter24` and `AnonIter32`, for code generators and
+//!   big dispatch tables. They are off by default because they take a while to compile. Enables `arity-12`.
+//! - `macros`: adds the `#[anon_iter::macros::anon]` attribute, which wraps every value
+//!   a function returns in a variant of the right enum, and `#[derive(anon_iter::macros::IteratorEnum)]`,
+//!   which forwards the iterator traits of your own enum to its variants, and `match_iters!`,
+//!   which wraps the arms of a `match` in an enum defined just for it.
+//! - `quickcheck`: implements [`quickcheck::Arbitrary`](https://docs.rs/quickcheck) for the enums.
+//!   Shrinking keeps the variant and shrinks its iterator.
+//! - `serde`: implements `Serialize` and `Deserialize` for the enums, adjacently tagged
+//!   as `{"variant": "I2", "iter": ...}`.
+//! - `std`: implements `std::io::Read`, `BufRead` and `Write` for the enums. Enables `alloc`.
+//! - `tracing`: adds `instrument`, which emits a [`tracing`](https://docs.rs/tracing) event with
+//!   the active variant and its item count once iteration ends.
+//!
+//! The MSRV is Rust 1.54 with the default features, and the optional integrations follow the MSRVs
+//! of their dependencies: `macros` needs Rust 1.61, for `syn` 2. Newer compilers also get
+//! `from_control_flow` (1.55), the `Error` impl (1.81) and the forwarding of `is_sorted`,
+//! `is_sorted_by` and `is_sorted_by_key` (1.82).
 #![no_std]
+#![cfg_attr(
+    feature = "nightly",
+    feature(
+        iter_advance_by,
+        iter_collect_into,
+        iter_is_partitioned,
+        iter_next_chunk,
+        iter_order_by,
+        min_specialization,
+        trusted_len,
+        try_trait_v2
+    )
+)]
+#![cfg_attr(
+    all(feature = "nightly", feature = "exact-size"),
+    feature(exact_size_is_empty)
+)]
+#![cfg_attr(all(feature = "nightly", feature = "fused"), feature(trusted_fused))]
 
-use core::iter::{DoubleEndedIterator, ExactSizeIterator, FusedIterator};
+#[cfg(any(feature = "alloc", feature = "quickcheck"))]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
-macro_rules! create {
-    ($count:literal, $AnonIter:ident, $($Variant:ident: $n:literal)*) => {
-        #[doc = concat!("Wraps ", $count, " `impl Iterator`s which may be of different types")]
-        ///
-        /// Functions returning `-> impl Iterator` must have the same return type
-        /// from all branches, but this is overly restrictive.
-        ///
-        /// We may want different iterators from the same function,
-        /// and this type allows that by wrapping each unique iterator in a variant of
-        /// this enum.
-        ///
-        /// See the [crate-level](crate) documentation for more info.
-        pub enum $AnonIter<T, $($Variant,)*>
-        where
-            $($Variant: Iterator<Item = T>,)*
-        {
-            $(
-                #[doc = concat!("The ", $n, " `impl Iterator`")]
-                $Variant($Variant),
-            )*
-        }
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt;
+use core::future::Future;
+use core::hash::{Hash, Hasher};
+use core::hint::unreachable_unchecked;
+#[cfg(feature = "nightly")]
+use core::iter::TrustedLen;
+use core::iter::{self, Empty};
+use core::marker::PhantomData;
+use core::mem::{self, MaybeUninit};
+#[cfg(anon_iter_control_flow)]
+use core::ops::ControlFlow;
+use core::pin::Pin;
 
-        #[allow(non_snake_case)]
-        impl<T, $($Variant: Iterator<Item = T>,)*> Iterator for $AnonIter<T, $($Variant,)*>
+/// Forwards one family of traits to the active variant of an enum whose variants each wrap one value
+///
+/// The families are the iterator traits (`Iterator` with its `Item`, `DoubleEndedIterator`,
+/// `ExactSizeIterator` and `FusedIterator`), the formatting traits (`Display` and the like),
+/// `Error`, `Future` with its `Output`, and `Read`, `BufRead` and `Write` from `std::io`.
+///
+/// The impls are generic over `generics` and bounded by `where` (which must end in a comma, if non-empty)
+/// and by each `Inner` implementing the trait. `nightly` is `[nightly]` to also forward the methods
+/// whose overrides need unstable features, if the `nightly` feature is enabled, and `[]` otherwise.
+///
+/// Used by the enums of this crate and by the code `anon_iter_macros` generates; not public API.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __forward {
+    (Iterator $nightly:tt [$($generics:tt)*] $Self:ty, [$($where:tt)*], $Item:ty, $($Variant:ident: $Inner:ty),*) => {
+        impl<$($generics)*> ::core::iter::Iterator for $Self
+        where
+            $($where)*
+            $($Inner: ::core::iter::Iterator,)*
         {
-            type Item = T;
+            type Item = $Item;
 
             fn next(&mut self) -> Option<Self::Item> {
                 match self {
                     $(
-                        Self::$Variant($Variant) => $Variant.next(),
+                        Self::$Variant(inner) => ::core::iter::Iterator::next(inner),
                     )*
                 }
             }
-        }
 
-        #[allow(non_snake_case)]
-        impl<T, $($Variant: DoubleEndedIterator<Item = T>,)*> DoubleEndedIterator for $AnonIter<T, $($Variant,)*>
-        {
-            fn next_back(&mut self) -> Option<Self::Item> {
+            fn size_hint(&self) -> (usize, Option<usize>) {
                 match self {
                     $(
-                        Self::$Variant($Variant) => $Variant.next_back(),
+                        Self::$Variant(inner) => ::core::iter::Iterator::size_hint(inner),
                     )*
                 }
             }
-        }
 
-        impl<T, $($Variant: FusedIterator<Item = T>,)*> FusedIterator for $AnonIter<T, $($Variant,)*> {}
+            fn fold<__B, __F>(self, init: __B, f: __F) -> __B
+            where
+                __F: FnMut(__B, Self::Item) -> __B,
+            {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::Iterator::fold(inner, init, f),
+                    )*
+                }
+            }
 
-        #[allow(non_snake_case)]
-        impl<T, $($Variant: ExactSizeIterator<Item = T>,)*> ExactSizeIterator for $AnonIter<T, $($Variant,)*>
-        {
-            fn len(&self) -> usize {
+            $crate::__nightly! {
+                $nightly
+                fn try_fold<__B, __F, __R>(&mut self, init: __B, f: __F) -> __R
+                where
+                    __F: FnMut(__B, Self::Item) -> __R,
+                    __R: ::core::ops::Try<Output = __B>,
+                {
+                    match self {
+                        $(
+                            Self::$Variant(inner) => ::core::iter::Iterator::try_fold(inner, init, f),
+                        )*
+                    }
+                }
+            }
+
+            fn nth(&mut self, n: usize) -> Option<Self::Item> {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::Iterator::nth(inner, n),
+                    )*
+                }
+            }
+
+            $crate::__nightly! {
+                $nightly
+                // Only on nightly, which is newer than the MSRV
+                #[allow(clippy::incompatible_msrv)]
+                fn advance_by(&mut self, n: usize) -> Result<(), ::core::num::NonZero<usize>> {
+                    match self {
+                        $(
+                            Self::$Variant(inner) => ::core::iter::Iterator::advance_by(inner, n),
+                        )*
+                    }
+                }
+            }
+
+            $crate::__nightly! {
+                $nightly
+                fn next_chunk<const __N: usize>(
+                    &mut self,
+                ) -> Result<[Self::Item; __N], ::core::array::IntoIter<Self::Item, __N>> {
+                    match self {
+                        $(
+                            Self::$Variant(inner) => ::core::iter::Iterator::next_chunk(inner),
+                        )*
+                    }
+                }
+            }
+
+            fn count(self) -> usize {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::Iterator::count(inner),
+                    )*
+                }
+            }
+
+            fn last(self) -> Option<Self::Item> {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::Iterator::last(inner),
+                    )*
+                }
+            }
+
+            fn for_each<__F>(self, f: __F)
+            where
+                __F: FnMut(Self::Item),
+            {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::Iterator::for_each(inner, f),
+                    )*
+                }
+            }
+
+            fn find<__P>(&mut self, predicate: __P) -> Option<Self::Item>
+            where
+                __P: FnMut(&Self::Item) -> bool,
+            {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::Iterator::find(inner, predicate),
+                    )*
+                }
+            }
+
+            fn position<__P>(&mut self, predicate: __P) -> Option<usize>
+            where
+                __P: FnMut(Self::Item) -> bool,
+            {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::Iterator::position(inner, predicate),
+                    )*
+                }
+            }
+
+            fn any<__F>(&mut self, f: __F) -> bool
+            where
+                __F: FnMut(Self::Item) -> bool,
+            {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::Iterator::any(inner, f),
+                    )*
+                }
+            }
+
+            fn all<__F>(&mut self, f: __F) -> bool
+            where
+                __F: FnMut(Self::Item) -> bool,
+            {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::Iterator::all(inner, f),
+                    )*
+                }
+            }
+
+            fn max(self) -> Option<Self::Item>
+            where
+                Self::Item: Ord,
+            {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::Iterator::max(inner),
+                    )*
+                }
+            }
+
+            fn min(self) -> Option<Self::Item>
+            where
+                Self::Item: Ord,
+            {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::Iterator::min(inner),
+                    )*
+                }
+            }
+
+            fn max_by_key<__B, __F>(self, f: __F) -> Option<Self::Item>
+            where
+                __B: Ord,
+                __F: FnMut(&Self::Item) -> __B,
+            {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::Iterator::max_by_key(inner, f),
+                    )*
+                }
+            }
+
+            fn max_by<__F>(self, compare: __F) -> Option<Self::Item>
+            where
+                __F: FnMut(&Self::Item, &Self::Item) -> ::core::cmp::Ordering,
+            {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::Iterator::max_by(inner, compare),
+                    )*
+                }
+            }
+
+            fn min_by_key<__B, __F>(self, f: __F) -> Option<Self::Item>
+            where
+                __B: Ord,
+                __F: FnMut(&Self::Item) -> __B,
+            {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::Iterator::min_by_key(inner, f),
+                    )*
+                }
+            }
+
+            fn min_by<__F>(self, compare: __F) -> Option<Self::Item>
+            where
+                __F: FnMut(&Self::Item, &Self::Item) -> ::core::cmp::Ordering,
+            {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::Iterator::min_by(inner, compare),
+                    )*
+                }
+            }
+
+            fn sum<__S>(self) -> __S
+            where
+                __S: ::core::iter::Sum<Self::Item>,
+            {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::Iterator::sum(inner),
+                    )*
+                }
+            }
+
+            fn product<__P>(self) -> __P
+            where
+                __P: ::core::iter::Product<Self::Item>,
+            {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::Iterator::product(inner),
+                    )*
+                }
+            }
+
+            fn reduce<__F>(self, f: __F) -> Option<Self::Item>
+            where
+                __F: FnMut(Self::Item, Self::Item) -> Self::Item,
+            {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::Iterator::reduce(inner, f),
+                    )*
+                }
+            }
+
+            $crate::__nightly! {
+                $nightly
+                fn try_for_each<__F, __R>(&mut self, f: __F) -> __R
+                where
+                    __F: FnMut(Self::Item) -> __R,
+                    __R: ::core::ops::Try<Output = ()>,
+                {
+                    match self {
+                        $(
+                            Self::$Variant(inner) => ::core::iter::Iterator::try_for_each(inner, f),
+                        )*
+                    }
+                }
+            }
+
+            // `__is_sorted!` drops these on compilers older than 1.82
+            $crate::__is_sorted! {
+                #[allow(clippy::incompatible_msrv)]
+                fn is_sorted(self) -> bool
+                where
+                    Self::Item: PartialOrd,
+                {
+                    match self {
+                        $(
+                            Self::$Variant(inner) => ::core::iter::Iterator::is_sorted(inner),
+                        )*
+                    }
+                }
+
+                #[allow(clippy::incompatible_msrv)]
+                fn is_sorted_by<__F>(self, compare: __F) -> bool
+                where
+                    __F: FnMut(&Self::Item, &Self::Item) -> bool,
+                {
+                    match self {
+                        $(
+                            Self::$Variant(inner) => ::core::iter::Iterator::is_sorted_by(inner, compare),
+                        )*
+                    }
+                }
+
+                #[allow(clippy::incompatible_msrv)]
+                fn is_sorted_by_key<__F, __K>(self, f: __F) -> bool
+                where
+                    __F: FnMut(Self::Item) -> __K,
+                    __K: PartialOrd,
+                {
+                    match self {
+                        $(
+                            Self::$Variant(inner) => ::core::iter::Iterator::is_sorted_by_key(inner, f),
+                        )*
+                    }
+                }
+            }
+
+            fn cmp<__I>(self, other: __I) -> ::core::cmp::Ordering
+            where
+                __I: IntoIterator<Item = Self::Item>,
+                Self::Item: Ord,
+            {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::Iterator::cmp(inner, other),
+                    )*
+                }
+            }
+
+            fn partial_cmp<__I>(self, other: __I) -> Option<::core::cmp::Ordering>
+            where
+                __I: IntoIterator,
+                Self::Item: PartialOrd<__I::Item>,
+            {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::Iterator::partial_cmp(inner, other),
+                    )*
+                }
+            }
+
+            fn eq<__I>(self, other: __I) -> bool
+            where
+                __I: IntoIterator,
+                Self::Item: PartialEq<__I::Item>,
+            {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::Iterator::eq(inner, other),
+                    )*
+                }
+            }
+
+            fn ne<__I>(self, other: __I) -> bool
+            where
+                __I: IntoIterator,
+                Self::Item: PartialEq<__I::Item>,
+            {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::Iterator::ne(inner, other),
+                    )*
+                }
+            }
+
+            fn lt<__I>(self, other: __I) -> bool
+            where
+                __I: IntoIterator,
+                Self::Item: PartialOrd<__I::Item>,
+            {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::Iterator::lt(inner, other),
+                    )*
+                }
+            }
+
+            fn le<__I>(self, other: __I) -> bool
+            where
+                __I: IntoIterator,
+                Self::Item: PartialOrd<__I::Item>,
+            {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::Iterator::le(inner, other),
+                    )*
+                }
+            }
+
+            fn gt<__I>(self, other: __I) -> bool
+            where
+                __I: IntoIterator,
+                Self::Item: PartialOrd<__I::Item>,
+            {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::Iterator::gt(inner, other),
+                    )*
+                }
+            }
+
+            fn ge<__I>(self, other: __I) -> bool
+            where
+                __I: IntoIterator,
+                Self::Item: PartialOrd<__I::Item>,
+            {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::Iterator::ge(inner, other),
+                    )*
+                }
+            }
+
+            fn partition<__B, __F>(self, f: __F) -> (__B, __B)
+            where
+                __B: Default + Extend<Self::Item>,
+                __F: FnMut(&Self::Item) -> bool,
+            {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::Iterator::partition(inner, f),
+                    )*
+                }
+            }
+
+            // `Iterator::unzip` can't be forwarded, because its `Self: Iterator<Item = (A, __B)>`
+            // bound doesn't carry over to the variants.
+            fn collect<__B>(self) -> __B
+            where
+                __B: ::core::iter::FromIterator<Self::Item>,
+            {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::Iterator::collect(inner),
+                    )*
+                }
+            }
+
+            fn find_map<__B, __F>(&mut self, f: __F) -> Option<__B>
+            where
+                __F: FnMut(Self::Item) -> Option<__B>,
+            {
                 match self {
                     $(
-                        Self::$Variant($Variant) => $Variant.len(),
+                        Self::$Variant(inner) => ::core::iter::Iterator::find_map(inner, f),
                     )*
                 }
             }
+
+            $crate::__nightly! {
+                $nightly
+                fn collect_into<__E>(self, collection: &mut __E) -> &mut __E
+                where
+                    __E: Extend<Self::Item>,
+                {
+                    match self {
+                        $(
+                            Self::$Variant(inner) => ::core::iter::Iterator::collect_into(inner, collection),
+                        )*
+                    }
+                }
+            }
+
+            $crate::__nightly! {
+                $nightly
+                fn is_partitioned<__P>(self, predicate: __P) -> bool
+                where
+                    __P: FnMut(Self::Item) -> bool,
+                {
+                    match self {
+                        $(
+                            Self::$Variant(inner) => ::core::iter::Iterator::is_partitioned(inner, predicate),
+                        )*
+                    }
+                }
+            }
+
+            $crate::__nightly! {
+                $nightly
+                fn cmp_by<__I, __F>(self, other: __I, cmp: __F) -> ::core::cmp::Ordering
+                where
+                    __I: IntoIterator,
+                    __F: FnMut(Self::Item, __I::Item) -> ::core::cmp::Ordering,
+                {
+                    match self {
+                        $(
+                            Self::$Variant(inner) => ::core::iter::Iterator::cmp_by(inner, other, cmp),
+                        )*
+                    }
+                }
+            }
+
+            $crate::__nightly! {
+                $nightly
+                fn partial_cmp_by<__I, __F>(self, other: __I, partial_cmp: __F) -> Option<::core::cmp::Ordering>
+                where
+                    __I: IntoIterator,
+                    __F: FnMut(Self::Item, __I::Item) -> Option<::core::cmp::Ordering>,
+                {
+                    match self {
+                        $(
+                            Self::$Variant(inner) => ::core::iter::Iterator::partial_cmp_by(inner, other, partial_cmp),
+                        )*
+                    }
+                }
+            }
+
+            $crate::__nightly! {
+                $nightly
+                fn eq_by<__I, __F>(self, other: __I, eq: __F) -> bool
+                where
+                    __I: IntoIterator,
+                    __F: FnMut(Self::Item, __I::Item) -> bool,
+                {
+                    match self {
+                        $(
+                            Self::$Variant(inner) => ::core::iter::Iterator::eq_by(inner, other, eq),
+                        )*
+                    }
+                }
+            }
         }
     };
-}
+    (DoubleEndedIterator $nightly:tt [$($generics:tt)*] $Self:ty, [$($where:tt)*], $Item:ty, $($Variant:ident: $Inner:ty),*) => {
+        impl<$($generics)*> ::core::iter::DoubleEndedIterator for $Self
+        where
+            $($where)*
+            $($Inner: ::core::iter::DoubleEndedIterator,)*
+        {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::DoubleEndedIterator::next_back(inner),
+                    )*
+                }
+            }
+
+            fn rfold<__B, __F>(self, init: __B, f: __F) -> __B
+            where
+                __F: FnMut(__B, Self::Item) -> __B,
+            {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::DoubleEndedIterator::rfold(inner, init, f),
+                    )*
+                }
+            }
+
+            $crate::__nightly! {
+                $nightly
+                fn try_rfold<__B, __F, __R>(&mut self, init: __B, f: __F) -> __R
+                where
+                    __F: FnMut(__B, Self::Item) -> __R,
+                    __R: ::core::ops::Try<Output = __B>,
+                {
+                    match self {
+                        $(
+                            Self::$Variant(inner) => ::core::iter::DoubleEndedIterator::try_rfold(inner, init, f),
+                        )*
+                    }
+                }
+            }
 
-create!(2, AnonIter2, I1: "1st" I2: "2nd");
-create!(3, AnonIter3, I1: "1st" I2: "2nd" I3: "3rd");
-create!(4, AnonIter4, I1: "1st" I2: "2nd" I3: "3rd" I4: "4th");
-create!(5, AnonIter5, I1: "1st" I2: "2nd" I3: "3rd" I4: "4th" I5: "5th");
-create!(6, AnonIter6, I1: "1st" I2: "2nd" I3: "3rd" I4: "4th" I5: "5th" I6: "6th");
-create!(7, AnonIter7, I1: "1st" I2: "2nd" I3: "3rd" I4: "4th" I5: "5th" I6: "6th" I7: "7th");
-create!(8, AnonIter8, I1: "1st" I2: "2nd" I3: "3rd" I4: "4th" I5: "5th" I6: "6th" I7: "7th" I8: "8th");
-create!(9, AnonIter9, I1: "1st" I2: "2nd" I3: "3rd" I4: "4th" I5: "5th" I6: "6th" I7: "7th" I8: "8th" I9: "9th");
-create!(10, AnonIter10, I1: "1st" I2: "2nd" I3: "3rd" I4: "4th" I5: "5th" I6: "6th" I7: "7th" I8: "8th" I9: "9th" I10: "10th");
-create!(11, AnonIter11, I1: "1st" I2: "2nd" I3: "3rd" I4: "4th" I5: "5th" I6: "6th" I7: "7th" I8: "8th" I9: "9th" I10: "10th" I11: "11th");
-create!(12, AnonIter12, I1: "1st" I2: "2nd" I3: "3rd" I4: "4th" I5: "5th" I6: "6th" I7: "7th" I8: "8th" I9: "9th" I10: "10th" I11: "11th" I12: "12th");
+            fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::DoubleEndedIterator::nth_back(inner, n),
+                    )*
+                }
+            }
+
+            $crate::__nightly! {
+                $nightly
+                // Only on nightly, which is newer than the MSRV
+                #[allow(clippy::incompatible_msrv)]
+                fn advance_back_by(&mut self, n: usize) -> Result<(), ::core::num::NonZero<usize>> {
+                    match self {
+                        $(
+                            Self::$Variant(inner) => ::core::iter::DoubleEndedIterator::advance_back_by(inner, n),
+                        )*
+                    }
+                }
+            }
+
+            // `Iterator::rposition` can't be forwarded as well, because its
+            // `Self: ExactSizeIterator + DoubleEndedIterator` bound says nothing
+            // about the variants. Its default implementation uses `try_rfold` instead.
+            fn rfind<__P>(&mut self, predicate: __P) -> Option<Self::Item>
+            where
+                __P: FnMut(&Self::Item) -> bool,
+            {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::DoubleEndedIterator::rfind(inner, predicate),
+                    )*
+                }
+            }
+        }
+    };
+    (ExactSizeIterator $nightly:tt [$($generics:tt)*] $Self:ty, [$($where:tt)*], $Item:ty, $($Variant:ident: $Inner:ty),*) => {
+        impl<$($generics)*> ::core::iter::ExactSizeIterator for $Self
+        where
+            $($where)*
+            $($Inner: ::core::iter::ExactSizeIterator,)*
+        {
+            fn len(&self) -> usize {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::iter::ExactSizeIterator::len(inner),
+                    )*
+                }
+            }
+
+            $crate::__nightly! {
+                $nightly
+                fn is_empty(&self) -> bool {
+                    match self {
+                        $(
+                            Self::$Variant(inner) => ::core::iter::ExactSizeIterator::is_empty(inner),
+                        )*
+                    }
+                }
+            }
+        }
+    };
+    (FusedIterator $nightly:tt [$($generics:tt)*] $Self:ty, [$($where:tt)*], $Item:ty, $($Variant:ident: $Inner:ty),*) => {
+        impl<$($generics)*> ::core::iter::FusedIterator for $Self
+        where
+            $($where)*
+            $($Inner: ::core::iter::FusedIterator,)*
+        {
+        }
+    };
+    (Display $($args:tt)*) => {
+        $crate::__forward!(@fmt Display $($args)*);
+    };
+    (Binary $($args:tt)*) => {
+        $crate::__forward!(@fmt Binary $($args)*);
+    };
+    (Octal $($args:tt)*) => {
+        $crate::__forward!(@fmt Octal $($args)*);
+    };
+    (LowerHex $($args:tt)*) => {
+        $crate::__forward!(@fmt LowerHex $($args)*);
+    };
+    (UpperHex $($args:tt)*) => {
+        $crate::__forward!(@fmt UpperHex $($args)*);
+    };
+    (LowerExp $($args:tt)*) => {
+        $crate::__forward!(@fmt LowerExp $($args)*);
+    };
+    (UpperExp $($args:tt)*) => {
+        $crate::__forward!(@fmt UpperExp $($args)*);
+    };
+    (@fmt $Trait:ident $nightly:tt [$($generics:tt)*] $Self:ty, [$($where:tt)*], $Item:ty, $($Variant:ident: $Inner:ty),*) => {
+        impl<$($generics)*> ::core::fmt::$Trait for $Self
+        where
+            $($where)*
+            $($Inner: ::core::fmt::$Trait,)*
+        {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::fmt::$Trait::fmt(inner, f),
+                    )*
+                }
+            }
+        }
+    };
+    (Error $nightly:tt [$($generics:tt)*] $Self:ty, [$($where:tt)*], $Item:ty, $($Variant:ident: $Inner:ty),*) => {
+        impl<$($generics)*> ::core::error::Error for $Self
+        where
+            $($where)*
+            $($Inner: ::core::error::Error,)*
+        {
+            fn source(&self) -> ::core::option::Option<&(dyn ::core::error::Error + 'static)> {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::core::error::Error::source(inner),
+                    )*
+                }
+            }
+        }
+    };
+    // The variants must be pinned structurally: `@pinned` checks that for enums outside this crate
+    (Future $nightly:tt [$($generics:tt)*] $Self:ty, [$($where:tt)*], $Output:ty, $($Variant:ident: $Inner:ty),*) => {
+        impl<$($generics)*> ::core::future::Future for $Self
+        where
+            $($where)*
+            $($Inner: ::core::future::Future,)*
+        {
+            type Output = $Output;
+
+            fn poll(
+                self: ::core::pin::Pin<&mut Self>,
+                cx: &mut ::core::task::Context<'_>,
+            ) -> ::core::task::Poll<Self::Output> {
+                // SAFETY: the variant is never moved out of `self`, which stays pinned
+                match unsafe { ::core::pin::Pin::get_unchecked_mut(self) } {
+                    $(
+                        Self::$Variant(inner) => {
+                            // SAFETY: the variants are pinned structurally
+                            ::core::future::Future::poll(unsafe { ::core::pin::Pin::new_unchecked(inner) }, cx)
+                        }
+                    )*
+                }
+            }
+        }
+    };
+    // Pins the variants structurally, like `pin-project` does: the enum is `Unpin` only when
+    // they all are (a manual `Unpin` impl would conflict), and it must not implement `Drop`,
+    // which could move a pinned variant
+    (@pinned [$($generics:tt)*] $Self:ty, [$($where:tt)*], $($Inner:ty),*) => {
+        impl<$($generics)*> ::core::marker::Unpin for $Self
+        where
+            $($where)*
+            $($Inner: ::core::marker::Unpin,)*
+        {
+        }
+
+        const _: () = {
+            trait MustNotImplDrop {}
+            #[allow(drop_bounds)]
+            impl<T: ::core::ops::Drop> MustNotImplDrop for T {}
+            impl<$($generics)*> MustNotImplDrop for $Self where $($where)* {}
+        };
+    };
+    (Read $nightly:tt [$($generics:tt)*] $Self:ty, [$($where:tt)*], $Item:ty, $($Variant:ident: $Inner:ty),*) => {
+        impl<$($generics)*> ::std::io::Read for $Self
+        where
+            $($where)*
+            $($Inner: ::std::io::Read,)*
+        {
+            fn read(&mut self, buf: &mut [u8]) -> ::std::io::Result<usize> {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::std::io::Read::read(inner, buf),
+                    )*
+                }
+            }
+
+            fn read_to_end(&mut self, buf: &mut ::std::vec::Vec<u8>) -> ::std::io::Result<usize> {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::std::io::Read::read_to_end(inner, buf),
+                    )*
+                }
+            }
+
+            fn read_to_string(&mut self, buf: &mut ::std::string::String) -> ::std::io::Result<usize> {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::std::io::Read::read_to_string(inner, buf),
+                    )*
+                }
+            }
+
+            fn read_exact(&mut self, buf: &mut [u8]) -> ::std::io::Result<()> {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::std::io::Read::read_exact(inner, buf),
+                    )*
+                }
+            }
+        }
+    };
+    (BufRead $nightly:tt [$($generics:tt)*] $Self:ty, [$($where:tt)*], $Item:ty, $($Variant:ident: $Inner:ty),*) => {
+        impl<$($generics)*> ::std::io::BufRead for $Self
+        where
+            $($where)*
+            $($Inner: ::std::io::BufRead,)*
+        {
+            fn fill_buf(&mut self) -> ::std::io::Result<&[u8]> {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::std::io::BufRead::fill_buf(inner),
+                    )*
+                }
+            }
+
+            fn consume(&mut self, amt: usize) {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::std::io::BufRead::consume(inner, amt),
+                    )*
+                }
+            }
+        }
+    };
+    (Write $nightly:tt [$($generics:tt)*] $Self:ty, [$($where:tt)*], $Item:ty, $($Variant:ident: $Inner:ty),*) => {
+        impl<$($generics)*> ::std::io::Write for $Self
+        where
+            $($where)*
+            $($Inner: ::std::io::Write,)*
+        {
+            fn write(&mut self, buf: &[u8]) -> ::std::io::Result<usize> {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::std::io::Write::write(inner, buf),
+                    )*
+                }
+            }
+
+            fn flush(&mut self) -> ::std::io::Result<()> {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::std::io::Write::flush(inner),
+                    )*
+                }
+            }
+
+            fn write_all(&mut self, buf: &[u8]) -> ::std::io::Result<()> {
+                match self {
+                    $(
+                        Self::$Variant(inner) => ::std::io::Write::write_all(inner, buf),
+                    )*
+                }
+            }
+        }
+    };
+}
+
+/// Defines an enum like the ones of this crate, with any number of variants and only the chosen traits
+///
+/// Each variant wraps an iterator of the same name, and the listed traits are forwarded to
+/// the active variant. They can be any of `Iterator`, `DoubleEndedIterator`, `ExactSizeIterator`,
+/// `FusedIterator`, `Display`, `Binary`, `Octal`, `LowerHex`, `UpperHex`, `LowerExp` and `UpperExp`.
+/// Attributes, such as `#[derive(Clone, Debug)]`, are added to the enum.
+///
+/// ```rust
+/// anon_iter::define_anon_iter! {
+///     /// Wraps one of 14 iterators, but can only be iterated forwards
+///     #[derive(Clone, Debug)]
+///     pub enum AnonIter14<I1, I2, I3, I4, I5, I6, I7, I8, I9, I10, I11, I12, I13, I14>: Iterator;
+/// }
+///
+/// type Numbers = AnonIter14<
+///     core::ops::Range<u32>,
+///     core::iter::Once<u32>,
+///     core::iter::Empty<u32>,
+///     core::iter::Empty<u32>,
+///     core::iter::Empty<u32>,
+///     core::iter::Empty<u32>,
+///     core::iter::Empty<u32>,
+///     core::iter::Empty<u32>,
+///     core::iter::Empty<u32>,
+///     core::iter::Empty<u32>,
+///     core::iter::Empty<u32>,
+///     core::iter::Empty<u32>,
+///     core::iter::Empty<u32>,
+///     core::iter::Empty<u32>,
+/// >;
+///
+/// assert_eq!(Numbers::I1(1..4).sum::<u32>(), 6);
+/// ```
+#[macro_export]
+macro_rules! define_anon_iter {
+    (
+        $(#[$attr:meta])*
+        $vis:vis enum $AnonIter:ident<$($Variant:ident),+ $(,)?>: $($Trait:ident),+ $(,)?;
+    ) => {
+        $(#[$attr])*
+        $vis enum $AnonIter<$($Variant,)+> {
+            $(
+                #[allow(missing_docs)]
+                $Variant($Variant),
+            )+
+        }
+
+        $crate::define_anon_iter!(@impls $AnonIter [$($Variant)+] $($Trait)+);
+    };
+    (@impls $AnonIter:ident $variants:tt $($Trait:ident)+) => {
+        $(
+            $crate::define_anon_iter!(@impl $Trait $AnonIter $variants);
+        )+
+    };
+    (@impl $Trait:ident $AnonIter:ident [$($Variant:ident)+]) => {
+        $crate::__forward!(
+            $Trait [] [__T, $($Variant,)+] $AnonIter<$($Variant,)+>,
+            [$($Variant: ::core::iter::Iterator<Item = __T>,)+], __T, $($Variant: $Variant),+
+        );
+    };
+}
+
+/// Expands to the given items if `flag` is `[nightly]` and the `nightly` feature is enabled
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "nightly")]
+macro_rules! __nightly {
+    ([nightly] $($item:tt)*) => {
+        $($item)*
+    };
+    ([] $($item:tt)*) => {};
+}
+
+/// Expands to the given items if `flag` is `[nightly]` and the `nightly` feature is enabled
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "nightly"))]
+macro_rules! __nightly {
+    ($flag:tt $($item:tt)*) => {};
+}
+
+/// Expands to the given items if the compiler has `Iterator::is_sorted`, stable since Rust 1.82
+#[doc(hidden)]
+#[macro_export]
+#[cfg(anon_iter_is_sorted)]
+macro_rules! __is_sorted {
+    ($($item:tt)*) => {
+        $($item)*
+    };
+}
+
+/// Expands to the given items if the compiler has `Iterator::is_sorted`, stable since Rust 1.82
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(anon_iter_is_sorted))]
+macro_rules! __is_sorted {
+    ($($item:tt)*) => {};
+}
+
+macro_rules! create {
+    (@rotate $AnonIter:ident $I1:ident) => {};
+    (@rotate $AnonIter:ident $I1:ident $I2:ident) => {};
+    (@rotate $AnonIter:ident $First:ident $($Variant:ident)*) => {
+        create!(@rotate $AnonIter $First [] [$($Variant)*] [$First $($Variant)*]);
+    };
+    (@rotate $AnonIter:ident $First:ident [$(($Next:ident $Variant:ident))*] [] [$Last:ident]) => {
+        #[allow(non_snake_case)]
+        impl<$First, $($Next,)*> $AnonIter<$First, $($Next,)*> {
+            /// Moves every value one variant to the left, with [`I1`](Self::I1)
+            /// wrapping around to become the last variant
+            pub fn rotate_left(self) -> $AnonIter<$($Next,)* $First> {
+                match self {
+                    Self::$First($First) => $AnonIter::$Last($First),
+                    $(
+                        Self::$Next($Next) => $AnonIter::$Variant($Next),
+                    )*
+                }
+            }
+
+            /// Moves every value one variant to the right, with the last variant
+            /// wrapping around to become [`I1`](Self::I1)
+            pub fn rotate_right(self) -> $AnonIter<$Last, $($Variant,)*> {
+                match self {
+                    Self::$Last($Last) => $AnonIter::$First($Last),
+                    $(
+                        Self::$Variant($Variant) => $AnonIter::$Next($Variant),
+                    )*
+                }
+            }
+        }
+    };
+    (@rotate $AnonIter:ident $First:ident [$($pairs:tt)*] [$Next:ident $($nexts:ident)*] [$Variant:ident $($variants:ident)*]) => {
+        create!(@rotate $AnonIter $First [$($pairs)* ($Next $Variant)] [$($nexts)*] [$($variants)*]);
+    };
+    (@widen [$($Small:ident $small:tt)*] $Large:ident $large:tt) => {
+        $(
+            create!(@widen $Small $small => $Large $large);
+            create!(@narrow $Large $large => $Small $small);
+        )*
+    };
+    (
+        @narrow $Large:ident [$($All:ident = $all_index:literal: $all_n:literal $all_mapped:tt $all_names:tt)*]
+        => $Small:ident [$($Variant:ident = $index:literal: $n:literal $mapped:tt $names:tt)*]
+    ) => {
+        /// Keeps the value in the same variant, or gives the value back if
+        /// its variant doesn't exist in the smaller enum
+        #[allow(non_snake_case)]
+        impl<$($All,)*> TryFrom<$Large<$($All,)*>> for $Small<$($Variant,)*> {
+            type Error = $Large<$($All,)*>;
+
+            fn try_from(large: $Large<$($All,)*>) -> Result<Self, Self::Error> {
+                match large {
+                    $(
+                        $Large::$Variant($Variant) => Ok(Self::$Variant($Variant)),
+                    )*
+                    other => Err(other),
+                }
+            }
+        }
+    };
+    (
+        @widen $Small:ident [$($Variant:ident = $index:literal: $n:literal $mapped:tt $names:tt)*]
+        => $Large:ident [$($All:ident = $all_index:literal: $all_n:literal $all_mapped:tt $all_names:tt)*]
+    ) => {
+        /// Keeps the value in the same variant
+        #[allow(non_snake_case)]
+        impl<$($All,)*> From<$Small<$($Variant,)*>> for $Large<$($All,)*> {
+            fn from(small: $Small<$($Variant,)*>) -> Self {
+                match small {
+                    $(
+                        $Small::$Variant($Variant) => Self::$Variant($Variant),
+                    )*
+                }
+            }
+        }
+    };
+    // A chain of 1 is the iterator itself, which can't implement `From` for `AnonIter1`
+    (@chain $AnonIter:ident $Only:ident) => {};
+    (@chain $AnonIter:ident $($Variant:ident)*) => {
+        /// Moves the value to the same position in the chain
+        #[allow(non_snake_case)]
+        impl<$($Variant,)*> From<$AnonIter<$($Variant,)*>> for create!(@chain_ty $($Variant)*) {
+            fn from(anon: $AnonIter<$($Variant,)*>) -> Self {
+                match anon {
+                    $(
+                        $AnonIter::$Variant($Variant) => chain::Inject::inject($Variant),
+                    )*
+                }
+            }
+        }
+    };
+    (@chain_ty $Last:ident) => { $Last };
+    (@chain_ty $First:ident $($Rest:ident)+) => { AnonChain<$First, create!(@chain_ty $($Rest)+)> };
+    (@wraps $count:literal $Only:ident) => {
+        "Holds a single value, so that code generated for any number of branches can treat a single branch the same"
+    };
+    (@wraps $count:literal $($Variant:ident)*) => {
+        concat!("Holds 1 of ", $count, " values which may be of different types")
+    };
+    (@replace $_Variant:ident $with:ty) => { $with };
+    (@map $AnonIter:ident; $($before:ident)*;) => {};
+    (@map $AnonIter:ident; $($before:ident)*; $Variant:ident $n:literal $map:ident $(, $after:ident $after_n:literal $after_map:ident)*) => {
+        #[doc = concat!("Applies `f` to the ", $n, " value, leaving the other variants untouched")]
+        pub fn $map<J>(self, f: impl FnOnce($Variant) -> J) -> $AnonIter<$($before,)* J, $($after,)*> {
+            match self {
+                $(
+                    Self::$before($before) => $AnonIter::$before($before),
+                )*
+                Self::$Variant($Variant) => $AnonIter::$Variant(f($Variant)),
+                $(
+                    Self::$after($after) => $AnonIter::$after($after),
+                )*
+            }
+        }
+
+        create!(@map $AnonIter; $($before)* $Variant; $($after $after_n $after_map),*);
+    };
+    (@ext $AnonIter:ident; $($before:ident)*;) => {};
+    (@ext $AnonIter:ident; $($before:ident)*; $Variant:ident $ext:ident $(, $after:ident $after_ext:ident)*) => {
+        #[doc = concat!("Wraps this iterator in [`", stringify!($AnonIter), "::", stringify!($Variant), "`]")]
+        fn $ext<$($before: Iterator<Item = Self::Item>,)* $($after: Iterator<Item = Self::Item>,)*>(self) -> $AnonIter<$($before,)* Self, $($after,)*> {
+            $AnonIter::$Variant(self)
+        }
+
+        create!(@ext $AnonIter; $($before)* $Variant; $($after $after_ext),*);
+    };
+    (@of $of:ident $AnonIter:ident, $($Variant:ident $fn:ident),*) => {
+        create!(@of $of $AnonIter [$($Variant)*], $($Variant $fn),*);
+    };
+    (@of $of:ident $AnonIter:ident $all:tt, $($Variant:ident $fn:ident),*) => {
+        #[doc = concat!("Functions wrapping an iterator in a variant of [`", stringify!($AnonIter), "`]")]
+        pub mod $of {
+            use crate::$AnonIter;
+
+            $(
+                create!(@of_fn $AnonIter $all $Variant $fn);
+            )*
+        }
+    };
+    (@of_fn $AnonIter:ident [$($All:ident)*] $Variant:ident $fn:ident) => {
+        #[doc = concat!("Wraps `iter` in [`", stringify!($AnonIter), "::", stringify!($Variant), "`]")]
+        pub fn $fn<$($All,)*>(iter: $Variant) -> $AnonIter<$($All,)*> {
+            $AnonIter::$Variant(iter)
+        }
+    };
+    (@select $AnonIter:ident, $I1:ident $i1:literal $I2:ident $i2:literal) => {};
+    (@select $AnonIter:ident, $($Variant:ident $index:literal)*) => {
+        #[allow(non_snake_case)]
+        impl<$($Variant,)*> $AnonIter<$($Variant,)*> {
+            /// Picks the iterator at the 1-based `index` of `iters`, or returns `None` if `index` is out of range
+            ///
+            /// The other iterators are dropped.
+            pub fn select(index: usize, iters: ($($Variant,)*)) -> Option<Self> {
+                let ($($Variant,)*) = iters;
+                match index {
+                    $(
+                        $index => Some(Self::$Variant($Variant)),
+                    )*
+                    _ => None,
+                }
+            }
+        }
+    };
+    (@default $AnonIter:ident, $First:ident $($Rest:ident)*) => {
+        /// Creates the 1st variant, holding the default value of its iterator
+        impl<$First: Default, $($Rest,)*> Default for $AnonIter<$First, $($Rest,)*> {
+            fn default() -> Self {
+                Self::$First($First::default())
+            }
+        }
+    };
+    (
+        $count:literal, $AnonIter:ident $Alias:ident $Into:ident $Hetero:ident $Item:ident $Try:ident $Err:ident,
+        $($Variant:ident = $index:literal: $n:literal ($Mapped:ident $f:ident) [$unwrap_unchecked:ident $as_unchecked:ident $as_mut_unchecked:ident $is:ident $as:ident $as_mut:ident $into:ident $unwrap:ident $expect:ident $map:ident])*
+    ) => {
+        #[doc = create!(@wraps $count $($Variant)*)]
+        ///
+        /// Like `Either`, it implements a trait whenever all of its variants do: the iterator traits,
+        /// the formatting traits, [`Future`] and [`Error`](core::error::Error), as well as `Read`,
+        /// `BufRead` and `Write` with the `std` feature.
+        ///
+        /// Functions returning `-> impl Iterator` must have the same return type
+        /// from all branches, but this is overly restrictive.
+        #[doc = concat!("Wrapping each unique iterator in a variant allows different iterators from the same function, which is why this enum is also named [`", stringify!($Alias), "`].")]
+        ///
+        /// See the [crate-level](crate) documentation for more info.
+        #[cfg_attr(
+            feature = "serde",
+            derive(serde::Serialize, serde::Deserialize),
+            serde(tag = "variant", content = "iter")
+        )]
+        pub enum $AnonIter<$($Variant,)*> {
+            $(
+                #[doc = concat!("The ", $n, " value")]
+                $Variant($Variant),
+            )*
+        }
+
+        #[doc = concat!("[`", stringify!($AnonIter), "`], named for its most common use: wrapping `impl Iterator`s")]
+        pub type $Alias<$($Variant,)*> = $AnonIter<$($Variant,)*>;
+
+        #[doc = concat!("[`AnonIterInto`] over [`", stringify!($AnonIter), "`], converting the items of every variant into `T`")]
+        pub type $Into<T, $($Variant,)*> = AnonIterInto<T, $AnonIter<$($Variant,)*>>;
+
+        impl<T, $($Variant: Iterator,)*> Iterator for AnonIterInto<T, $AnonIter<$($Variant,)*>>
+        where
+            $($Variant::Item: Into<T>,)*
+        {
+            type Item = T;
+
+            fn next(&mut self) -> Option<T> {
+                match &mut self.anon {
+                    $(
+                        $AnonIter::$Variant(inner) => inner.next().map(Into::into),
+                    )*
+                }
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                match &self.anon {
+                    $(
+                        $AnonIter::$Variant(inner) => inner.size_hint(),
+                    )*
+                }
+            }
+
+            fn fold<B, F: FnMut(B, T) -> B>(self, init: B, mut f: F) -> B {
+                match self.anon {
+                    $(
+                        $AnonIter::$Variant(inner) => inner.fold(init, |acc, item| f(acc, item.into())),
+                    )*
+                }
+            }
+        }
+
+        #[cfg(feature = "double-ended")]
+        impl<T, $($Variant: DoubleEndedIterator,)*> DoubleEndedIterator for AnonIterInto<T, $AnonIter<$($Variant,)*>>
+        where
+            $($Variant::Item: Into<T>,)*
+        {
+            fn next_back(&mut self) -> Option<T> {
+                match &mut self.anon {
+                    $(
+                        $AnonIter::$Variant(inner) => inner.next_back().map(Into::into),
+                    )*
+                }
+            }
+        }
+
+        #[cfg(feature = "exact-size")]
+        impl<T, $($Variant: ExactSizeIterator,)*> ExactSizeIterator for AnonIterInto<T, $AnonIter<$($Variant,)*>>
+        where
+            $($Variant::Item: Into<T>,)*
+        {
+        }
+
+        #[cfg(feature = "fused")]
+        impl<T, $($Variant: iter::FusedIterator,)*> iter::FusedIterator for AnonIterInto<T, $AnonIter<$($Variant,)*>>
+        where
+            $($Variant::Item: Into<T>,)*
+        {
+        }
+
+        #[doc = concat!("[`AnonIterHetero`] over [`", stringify!($AnonIter), "`], wrapping every item in the variant its iterator is in")]
+        pub type $Hetero<$($Variant,)*> = AnonIterHetero<$AnonIter<$($Variant,)*>>;
+
+        #[doc = concat!("[`", stringify!($AnonIter), "`], named for the items of [`", stringify!($Hetero), "`]")]
+        pub type $Item<$($Variant,)*> = $AnonIter<$($Variant,)*>;
+
+        impl<$($Variant: Iterator,)*> Iterator for AnonIterHetero<$AnonIter<$($Variant,)*>> {
+            type Item = $AnonIter<$($Variant::Item,)*>;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                match &mut self.anon {
+                    $(
+                        $AnonIter::$Variant(inner) => inner.next().map($AnonIter::$Variant),
+                    )*
+                }
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                match &self.anon {
+                    $(
+                        $AnonIter::$Variant(inner) => inner.size_hint(),
+                    )*
+                }
+            }
+
+            fn fold<B, F: FnMut(B, Self::Item) -> B>(self, init: B, mut f: F) -> B {
+                match self.anon {
+                    $(
+                        $AnonIter::$Variant(inner) => inner.fold(init, |acc, item| f(acc, $AnonIter::$Variant(item))),
+                    )*
+                }
+            }
+        }
+
+        #[cfg(feature = "double-ended")]
+        impl<$($Variant: DoubleEndedIterator,)*> DoubleEndedIterator for AnonIterHetero<$AnonIter<$($Variant,)*>> {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                match &mut self.anon {
+                    $(
+                        $AnonIter::$Variant(inner) => inner.next_back().map($AnonIter::$Variant),
+                    )*
+                }
+            }
+        }
+
+        #[cfg(feature = "exact-size")]
+        impl<$($Variant: ExactSizeIterator,)*> ExactSizeIterator for AnonIterHetero<$AnonIter<$($Variant,)*>> {}
+
+        #[cfg(feature = "fused")]
+        impl<$($Variant: iter::FusedIterator,)*> iter::FusedIterator for AnonIterHetero<$AnonIter<$($Variant,)*>> {}
+
+        #[doc = concat!("[`AnonTryIter`] over [`", stringify!($AnonIter), "`], wrapping every error in the variant its iterator is in")]
+        pub type $Try<$($Variant,)*> = AnonTryIter<$AnonIter<$($Variant,)*>>;
+
+        #[doc = concat!("[`", stringify!($AnonIter), "`], named for the errors of [`", stringify!($Try), "`]")]
+        pub type $Err<$($Variant,)*> = $AnonIter<$($Variant,)*>;
+
+        // `J1`, `J2` and so on are free here, so they name the error of each variant
+        impl<T, $($Mapped,)* $($Variant: Iterator<Item = Result<T, $Mapped>>,)*> Iterator for AnonTryIter<$AnonIter<$($Variant,)*>> {
+            type Item = Result<T, $AnonIter<$($Mapped,)*>>;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                match &mut self.anon {
+                    $(
+                        $AnonIter::$Variant(inner) => inner.next().map(|item| item.map_err($AnonIter::$Variant)),
+                    )*
+                }
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                match &self.anon {
+                    $(
+                        $AnonIter::$Variant(inner) => inner.size_hint(),
+                    )*
+                }
+            }
+
+            fn fold<B, F: FnMut(B, Self::Item) -> B>(self, init: B, mut f: F) -> B {
+                match self.anon {
+                    $(
+                        $AnonIter::$Variant(inner) => inner.fold(init, |acc, item| f(acc, item.map_err($AnonIter::$Variant))),
+                    )*
+                }
+            }
+        }
+
+        #[cfg(feature = "double-ended")]
+        impl<T, $($Mapped,)* $($Variant: DoubleEndedIterator<Item = Result<T, $Mapped>>,)*> DoubleEndedIterator for AnonTryIter<$AnonIter<$($Variant,)*>> {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                match &mut self.anon {
+                    $(
+                        $AnonIter::$Variant(inner) => inner.next_back().map(|item| item.map_err($AnonIter::$Variant)),
+                    )*
+                }
+            }
+        }
+
+        #[cfg(feature = "exact-size")]
+        impl<T, $($Mapped,)* $($Variant: ExactSizeIterator<Item = Result<T, $Mapped>>,)*> ExactSizeIterator for AnonTryIter<$AnonIter<$($Variant,)*>> {}
+
+        #[cfg(feature = "fused")]
+        impl<T, $($Mapped,)* $($Variant: iter::FusedIterator<Item = Result<T, $Mapped>>,)*> iter::FusedIterator for AnonTryIter<$AnonIter<$($Variant,)*>> {}
+
+        // The catch-all arms can't be reached in `AnonIter1`
+        #[allow(unreachable_patterns)]
+        impl<T, $($Variant: Iterator<Item = T>,)*> AnonIterLite<$AnonIter<$($Variant,)*>> {
+            // Every entry is only called through the index of the active variant
+            const VTABLE: LiteVTable<$AnonIter<$($Variant,)*>, T, $count> = LiteVTable {
+                size_hint: [$(
+                    |anon| match anon {
+                        $AnonIter::$Variant(inner) => inner.size_hint(),
+                        _ => unreachable!(),
+                    },
+                )*],
+                nth: [$(
+                    |anon, n| match anon {
+                        $AnonIter::$Variant(inner) => inner.nth(n),
+                        _ => unreachable!(),
+                    },
+                )*],
+                count: [$(
+                    |anon| match anon {
+                        $AnonIter::$Variant(inner) => inner.count(),
+                        _ => unreachable!(),
+                    },
+                )*],
+            };
+        }
+
+        impl<T, $($Variant: Iterator<Item = T>,)*> Iterator for AnonIterLite<$AnonIter<$($Variant,)*>> {
+            type Item = T;
+
+            fn next(&mut self) -> Option<T> {
+                match &mut self.anon {
+                    $(
+                        $AnonIter::$Variant(inner) => inner.next(),
+                    )*
+                }
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (Self::VTABLE.size_hint[self.anon.variant_index() - 1])(&self.anon)
+            }
+
+            fn nth(&mut self, n: usize) -> Option<T> {
+                (Self::VTABLE.nth[self.anon.variant_index() - 1])(&mut self.anon, n)
+            }
+
+            fn count(self) -> usize {
+                (Self::VTABLE.count[self.anon.variant_index() - 1])(self.anon)
+            }
+        }
+
+        #[cfg(feature = "double-ended")]
+        impl<T, $($Variant: DoubleEndedIterator<Item = T>,)*> DoubleEndedIterator for AnonIterLite<$AnonIter<$($Variant,)*>> {
+            fn next_back(&mut self) -> Option<T> {
+                match &mut self.anon {
+                    $(
+                        $AnonIter::$Variant(inner) => inner.next_back(),
+                    )*
+                }
+            }
+        }
+
+        #[cfg(feature = "exact-size")]
+        impl<T, $($Variant: ExactSizeIterator<Item = T>,)*> ExactSizeIterator for AnonIterLite<$AnonIter<$($Variant,)*>> {}
+
+        #[cfg(feature = "fused")]
+        impl<T, $($Variant: iter::FusedIterator<Item = T>,)*> iter::FusedIterator for AnonIterLite<$AnonIter<$($Variant,)*>> {}
+
+        // The catch-all arms can't be reached in `AnonIter1`
+        #[allow(non_snake_case, unreachable_patterns)]
+        impl<$($Variant,)*> $AnonIter<$($Variant,)*> {
+            /// Returns the 1-based index of the active variant, e.g. `1` for [`I1`](Self::I1)
+            pub fn variant_index(&self) -> usize {
+                match self {
+                    $(
+                        Self::$Variant(_) => $index,
+                    )*
+                }
+            }
+
+            /// Returns the name of the active variant, e.g. `"I1"` for [`I1`](Self::I1)
+            pub fn variant_name(&self) -> &'static str {
+                match self {
+                    $(
+                        Self::$Variant(_) => stringify!($Variant),
+                    )*
+                }
+            }
+
+            /// Returns the active variant's name and index, formatted even when the variants aren't [`Debug`]
+            ///
+            /// Useful for logging which branch was taken.
+            pub fn debug_variant(&self) -> DebugVariant {
+                DebugVariant {
+                    name: self.variant_name(),
+                    index: self.variant_index(),
+                }
+            }
+
+            /// Converts the active variant's value into `C`
+            ///
+            /// This collapses the enum into a single type, such as a concrete iterator
+            /// or a boxed one, when every variant can be converted into it.
+            pub fn into_common<C>(self) -> C
+            where
+                $($Variant: Into<C>,)*
+            {
+                match self {
+                    $(
+                        Self::$Variant($Variant) => $Variant.into(),
+                    )*
+                }
+            }
+
+            /// Converts `self` into an enum with fewer variants, keeping the active variant
+            ///
+            /// Gives `self` back if the active variant is one of those being dropped.
+            pub fn try_narrow<Narrow: TryFrom<Self, Error = Self>>(self) -> Result<Narrow, Self> {
+                Narrow::try_from(self)
+            }
+
+            /// Returns an enum of mutable references to the wrapped values,
+            /// to iterate through `self` without consuming it
+            pub fn as_mut(&mut self) -> $AnonIter<$(&mut $Variant,)*> {
+                match self {
+                    $(
+                        Self::$Variant($Variant) => $AnonIter::$Variant($Variant),
+                    )*
+                }
+            }
+
+            /// Returns an enum of pinned mutable references to the wrapped values
+            ///
+            /// This projects the pin of `self` onto the active variant, for inner types that must stay pinned.
+            pub fn as_pin_mut(self: Pin<&mut Self>) -> $AnonIter<$(Pin<&mut $Variant>,)*> {
+                // SAFETY: the variant is never moved out of a pinned `self`. The enum has no `Drop` impl
+                // that could move it, and is only `Unpin` when every variant is.
+                match unsafe { self.get_unchecked_mut() } {
+                    $(
+                        Self::$Variant($Variant) => $AnonIter::$Variant(unsafe { Pin::new_unchecked($Variant) }),
+                    )*
+                }
+            }
+
+            /// Converts the items of every variant into `T`, for variants that yield different types
+            pub fn items_into<T>(self) -> AnonIterInto<T, Self> {
+                AnonIterInto::new(self)
+            }
+
+            /// Wraps every item in the variant its iterator is in, for variants that yield unrelated types
+            pub fn anon_items(self) -> AnonIterHetero<Self> {
+                AnonIterHetero::new(self)
+            }
+
+            /// Wraps every error in the variant its iterator is in, for fallible variants whose error types differ
+            pub fn anon_errors(self) -> AnonTryIter<Self> {
+                AnonTryIter::new(self)
+            }
+
+            /// Forwards only `next` and `next_back`, and dispatches a few cold methods through a table of function pointers
+            pub fn lite(self) -> AnonIterLite<Self> {
+                AnonIterLite::new(self)
+            }
+
+            /// Calls `into_iter` on the active variant, so that branches can hold collections such as a `Vec`
+            ///
+            /// The enum can't implement [`IntoIterator`] for variants that are only `IntoIterator`,
+            /// as that would overlap with the impl every [`Iterator`] gets.
+            pub fn into_iters(self) -> $AnonIter<$(<$Variant as IntoIterator>::IntoIter,)*>
+            where
+                $($Variant: IntoIterator,)*
+            {
+                match self {
+                    $(
+                        Self::$Variant(inner) => $AnonIter::$Variant(inner.into_iter()),
+                    )*
+                }
+            }
+
+            create!(@map $AnonIter; ; $($Variant $n $map),*);
+
+            /// Applies the closure corresponding to the active variant to its value
+            ///
+            /// `f1` is applied to [`I1`](Self::I1), `f2` to `I2` and so on.
+            #[allow(clippy::too_many_arguments)]
+            pub fn map_all<$($Mapped,)*>(self, $($f: impl FnOnce($Variant) -> $Mapped,)*) -> $AnonIter<$($Mapped,)*> {
+                match self {
+                    $(
+                        Self::$Variant($Variant) => $AnonIter::$Variant($f($Variant)),
+                    )*
+                }
+            }
+
+            /// Calls the closure corresponding to the active variant with its value,
+            /// returning the closure's result
+            ///
+            /// `f1` is called for [`I1`](Self::I1), `f2` for `I2` and so on.
+            #[allow(clippy::too_many_arguments)]
+            pub fn fold_variants<R>(self, $($f: impl FnOnce($Variant) -> R,)*) -> R {
+                match self {
+                    $(
+                        Self::$Variant($Variant) => $f($Variant),
+                    )*
+                }
+            }
+
+            $(
+                #[doc = concat!("Returns `true` if `self` holds the ", $n, " value")]
+                pub fn $is(&self) -> bool {
+                    matches!(self, Self::$Variant(_))
+                }
+
+                #[doc = concat!("Returns a reference to the ", $n, " value, or `None` if `self` holds another one")]
+                pub fn $as(&self) -> Option<&$Variant> {
+                    match self {
+                        Self::$Variant($Variant) => Some($Variant),
+                        _ => None,
+                    }
+                }
+
+                #[doc = concat!("Returns a mutable reference to the ", $n, " value, or `None` if `self` holds another one")]
+                pub fn $as_mut(&mut self) -> Option<&mut $Variant> {
+                    match self {
+                        Self::$Variant($Variant) => Some($Variant),
+                        _ => None,
+                    }
+                }
+
+                #[doc = concat!("Returns the ", $n, " value, or gives `self` back if it holds another one")]
+                pub fn $into(self) -> Result<$Variant, Self> {
+                    match self {
+                        Self::$Variant($Variant) => Ok($Variant),
+                        other => Err(other),
+                    }
+                }
+
+                #[doc = concat!("Returns the ", $n, " value, consuming `self`")]
+                ///
+                /// # Panics
+                ///
+                #[doc = concat!("If `self` holds another variant than [`", stringify!($Variant), "`](Self::", stringify!($Variant), ")")]
+                #[track_caller]
+                pub fn $unwrap(self) -> $Variant {
+                    match self {
+                        Self::$Variant($Variant) => $Variant,
+                        other => panic!(
+                            concat!("called `", stringify!($AnonIter), "::", stringify!($unwrap), "()` on an `{}` value"),
+                            other.variant_name()
+                        ),
+                    }
+                }
+
+                #[doc = concat!("Returns the ", $n, " value, consuming `self`")]
+                ///
+                /// # Panics
+                ///
+                #[doc = concat!("If `self` holds another variant than [`", stringify!($Variant), "`](Self::", stringify!($Variant), "),")]
+                /// with `msg` as the panic message
+                #[track_caller]
+                pub fn $expect(self, msg: &str) -> $Variant {
+                    match self {
+                        Self::$Variant($Variant) => $Variant,
+                        _ => panic!("{}", msg),
+                    }
+                }
+
+                #[doc = concat!("Returns the ", $n, " value, consuming `self`, without checking that `self` holds it")]
+                ///
+                /// # Safety
+                ///
+                #[doc = concat!("Calling this method on any variant other than [`", stringify!($Variant), "`](Self::", stringify!($Variant), ")")]
+                /// is *[undefined behavior]*.
+                ///
+                /// [undefined behavior]: https://doc.rust-lang.org/reference/behavior-considered-undefined.html
+                #[allow(unused_unsafe)]
+                pub unsafe fn $unwrap_unchecked(self) -> $Variant {
+                    match self {
+                        Self::$Variant($Variant) => $Variant,
+                        // SAFETY: the caller guarantees that `self` is this variant
+                        _ => unsafe { unreachable_unchecked() },
+                    }
+                }
+
+                #[doc = concat!("Returns a reference to the ", $n, " value, without checking that `self` holds it")]
+                ///
+                /// # Safety
+                ///
+                #[doc = concat!("Calling this method on any variant other than [`", stringify!($Variant), "`](Self::", stringify!($Variant), ")")]
+                /// is *[undefined behavior]*.
+                ///
+                /// [undefined behavior]: https://doc.rust-lang.org/reference/behavior-considered-undefined.html
+                #[allow(unused_unsafe)]
+                pub unsafe fn $as_unchecked(&self) -> &$Variant {
+                    match self {
+                        Self::$Variant($Variant) => $Variant,
+                        // SAFETY: the caller guarantees that `self` is this variant
+                        _ => unsafe { unreachable_unchecked() },
+                    }
+                }
+
+                #[doc = concat!("Returns a mutable reference to the ", $n, " value, without checking that `self` holds it")]
+                ///
+                /// # Safety
+                ///
+                #[doc = concat!("Calling this method on any variant other than [`", stringify!($Variant), "`](Self::", stringify!($Variant), ")")]
+                /// is *[undefined behavior]*.
+                ///
+                /// [undefined behavior]: https://doc.rust-lang.org/reference/behavior-considered-undefined.html
+                #[allow(unused_unsafe)]
+                pub unsafe fn $as_mut_unchecked(&mut self) -> &mut $Variant {
+                    match self {
+                        Self::$Variant($Variant) => $Variant,
+                        // SAFETY: the caller guarantees that `self` is this variant
+                        _ => unsafe { unreachable_unchecked() },
+                    }
+                }
+            )*
+        }
+
+        impl<T, $($Variant: Iterator<Item = T>,)*> $AnonIter<$($Variant,)*> {
+            /// Calls `f` with the active variant and the number of items it yielded, once iteration ends
+            ///
+            /// Iteration ends when `next` first returns `None`, or when the adapter is dropped.
+            /// This shows which branches dominate without writing a wrapper by hand.
+            pub fn inspect_variant<F: FnOnce(DebugVariant, usize)>(self, f: F) -> InspectVariant<Self, F> {
+                InspectVariant {
+                    variant: self.debug_variant(),
+                    iter: self,
+                    count: 0,
+                    f: Some(f),
+                }
+            }
+
+            /// Emits a `tracing` event with the active variant and the number of items it yielded,
+            /// once iteration ends
+            ///
+            /// The event is at the `DEBUG` level, and `name` tells apart the events of different iterators.
+            #[cfg(feature = "tracing")]
+            pub fn instrument(self, name: &'static str) -> InspectVariant<Self, impl FnOnce(DebugVariant, usize)> {
+                self.inspect_variant(move |variant, items| {
+                    tracing::debug!(
+                        iter = name,
+                        variant = variant.name(),
+                        index = variant.index(),
+                        items,
+                        "iteration ended"
+                    );
+                })
+            }
+
+            /// Erases the type of `self` by boxing it, for when the enum shouldn't be part of an API
+            #[cfg(feature = "alloc")]
+            pub fn boxed<'a>(self) -> Box<dyn Iterator<Item = T> + Send + 'a>
+            where
+                Self: Send + 'a,
+            {
+                Box::new(self)
+            }
+
+            /// Like [`boxed`](Self::boxed), for iterators that aren't [`Send`]
+            #[cfg(feature = "alloc")]
+            pub fn boxed_local<'a>(self) -> Box<dyn Iterator<Item = T> + 'a>
+            where
+                Self: 'a,
+            {
+                Box::new(self)
+            }
+        }
+
+        impl<I> $AnonIter<$(create!(@replace $Variant I),)*> {
+            /// Returns the wrapped value, when every variant holds the same type
+            pub fn into_inner(self) -> I {
+                match self {
+                    $(
+                        Self::$Variant(iter) => iter,
+                    )*
+                }
+            }
+        }
+
+        $crate::__forward!(Iterator [nightly] [T, $($Variant,)*] $AnonIter<$($Variant,)*>, [$($Variant: Iterator<Item = T>,)*], T, $($Variant: $Variant),*);
+        #[cfg(feature = "double-ended")]
+        $crate::__forward!(DoubleEndedIterator [nightly] [T, $($Variant,)*] $AnonIter<$($Variant,)*>, [$($Variant: Iterator<Item = T>,)*], T, $($Variant: $Variant),*);
+        #[cfg(feature = "fused")]
+        $crate::__forward!(FusedIterator [nightly] [T, $($Variant,)*] $AnonIter<$($Variant,)*>, [$($Variant: Iterator<Item = T>,)*], T, $($Variant: $Variant),*);
+
+        // SAFETY: `size_hint` is forwarded to the active variant, which upholds the
+        // `TrustedLen` contract itself.
+        #[cfg(feature = "nightly")]
+        unsafe impl<T, $($Variant: TrustedLen<Item = T>,)*> TrustedLen for $AnonIter<$($Variant,)*> {}
+
+        // SAFETY: once exhausted, the active variant keeps returning `None`.
+        #[cfg(all(feature = "nightly", feature = "fused"))]
+        unsafe impl<$($Variant: iter::TrustedFused,)*> iter::TrustedFused for $AnonIter<$($Variant,)*> {}
+
+        #[cfg(feature = "exact-size")]
+        $crate::__forward!(ExactSizeIterator [nightly] [T, $($Variant,)*] $AnonIter<$($Variant,)*>, [$($Variant: Iterator<Item = T>,)*], T, $($Variant: $Variant),*);
+
+        /// Iterates over a borrow of the active variant, e.g. a `&Vec<T>` or a `&[T]`
+        ///
+        /// `&mut` borrows can't have the same impl, as it would overlap with the one they get
+        /// from being an [`Iterator`] whenever the enum is. Use `as_mut().into_iters()` instead.
+        #[allow(non_snake_case)]
+        impl<'a, U, $($Variant,)*> IntoIterator for &'a $AnonIter<$($Variant,)*>
+        where
+            $(&'a $Variant: IntoIterator<Item = U>,)*
+        {
+            type Item = U;
+            type IntoIter = $AnonIter<$(<&'a $Variant as IntoIterator>::IntoIter,)*>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                match self {
+                    $(
+                        $AnonIter::$Variant($Variant) => $AnonIter::$Variant($Variant.into_iter()),
+                    )*
+                }
+            }
+        }
+
+        #[allow(non_snake_case)]
+        impl<$($Variant: Clone,)*> Clone for $AnonIter<$($Variant,)*>
+        {
+            fn clone(&self) -> Self {
+                match self {
+                    $(
+                        Self::$Variant($Variant) => Self::$Variant($Variant.clone()),
+                    )*
+                }
+            }
+        }
+
+        impl<$($Variant: Copy,)*> Copy for $AnonIter<$($Variant,)*> {}
+
+        #[allow(non_snake_case)]
+        impl<$($Variant: fmt::Debug,)*> fmt::Debug for $AnonIter<$($Variant,)*>
+        {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                    $(
+                        Self::$Variant($Variant) => f.debug_tuple(stringify!($Variant)).field($Variant).finish(),
+                    )*
+                }
+            }
+        }
+
+        #[cfg(feature = "fmt")]
+        $crate::__forward!(Display [nightly] [$($Variant,)*] $AnonIter<$($Variant,)*>, [], (), $($Variant: $Variant),*);
+        #[cfg(feature = "fmt")]
+        $crate::__forward!(Binary [nightly] [$($Variant,)*] $AnonIter<$($Variant,)*>, [], (), $($Variant: $Variant),*);
+        #[cfg(feature = "fmt")]
+        $crate::__forward!(Octal [nightly] [$($Variant,)*] $AnonIter<$($Variant,)*>, [], (), $($Variant: $Variant),*);
+        #[cfg(feature = "fmt")]
+        $crate::__forward!(LowerHex [nightly] [$($Variant,)*] $AnonIter<$($Variant,)*>, [], (), $($Variant: $Variant),*);
+        #[cfg(feature = "fmt")]
+        $crate::__forward!(UpperHex [nightly] [$($Variant,)*] $AnonIter<$($Variant,)*>, [], (), $($Variant: $Variant),*);
+        #[cfg(feature = "fmt")]
+        $crate::__forward!(LowerExp [nightly] [$($Variant,)*] $AnonIter<$($Variant,)*>, [], (), $($Variant: $Variant),*);
+        #[cfg(feature = "fmt")]
+        $crate::__forward!(UpperExp [nightly] [$($Variant,)*] $AnonIter<$($Variant,)*>, [], (), $($Variant: $Variant),*);
+
+        #[cfg(all(feature = "fmt", anon_iter_core_error))]
+        $crate::__forward!(Error [] [$($Variant,)*] $AnonIter<$($Variant,)*>, [], (), $($Variant: $Variant),*);
+
+        // `Unpin` is the auto impl and there is no `Drop` impl, so the variants are pinned structurally
+        $crate::__forward!(Future [] [T, $($Variant,)*] $AnonIter<$($Variant,)*>, [$($Variant: Future<Output = T>,)*], T, $($Variant: $Variant),*);
+
+        #[cfg(feature = "std")]
+        $crate::__forward!(Read [] [$($Variant,)*] $AnonIter<$($Variant,)*>, [], (), $($Variant: $Variant),*);
+        #[cfg(feature = "std")]
+        $crate::__forward!(BufRead [] [$($Variant,)*] $AnonIter<$($Variant,)*>, [], (), $($Variant: $Variant),*);
+        #[cfg(feature = "std")]
+        $crate::__forward!(Write [] [$($Variant,)*] $AnonIter<$($Variant,)*>, [], (), $($Variant: $Variant),*);
+
+        #[allow(non_snake_case, unreachable_patterns)]
+        impl<$($Variant: PartialEq,)*> PartialEq for $AnonIter<$($Variant,)*>
+        {
+            fn eq(&self, other: &Self) -> bool {
+                match (self, other) {
+                    $(
+                        (Self::$Variant($Variant), Self::$Variant(other)) => $Variant == other,
+                    )*
+                    _ => false,
+                }
+            }
+        }
+
+        impl<$($Variant: Eq,)*> Eq for $AnonIter<$($Variant,)*> {}
+
+        #[allow(non_snake_case)]
+        impl<$($Variant: Hash,)*> Hash for $AnonIter<$($Variant,)*>
+        {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                mem::discriminant(self).hash(state);
+                match self {
+                    $(
+                        Self::$Variant($Variant) => $Variant.hash(state),
+                    )*
+                }
+            }
+        }
+
+        #[allow(non_snake_case, unreachable_patterns)]
+        impl<$($Variant: PartialOrd,)*> PartialOrd for $AnonIter<$($Variant,)*>
+        {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                match (self, other) {
+                    $(
+                        (Self::$Variant($Variant), Self::$Variant(other)) => $Variant.partial_cmp(other),
+                    )*
+                    _ => self.variant_index().partial_cmp(&other.variant_index()),
+                }
+            }
+        }
+
+        #[allow(non_snake_case, unreachable_patterns)]
+        impl<$($Variant: Ord,)*> Ord for $AnonIter<$($Variant,)*>
+        {
+            fn cmp(&self, other: &Self) -> Ordering {
+                match (self, other) {
+                    $(
+                        (Self::$Variant($Variant), Self::$Variant(other)) => $Variant.cmp(other),
+                    )*
+                    _ => self.variant_index().cmp(&other.variant_index()),
+                }
+            }
+        }
+
+        /// Picks a variant, then generates its iterator
+        #[cfg(feature = "arbitrary")]
+        impl<'a, $($Variant: arbitrary::Arbitrary<'a>,)*> arbitrary::Arbitrary<'a> for $AnonIter<$($Variant,)*>
+        {
+            fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+                Ok(match u.choose_index($count)? + 1 {
+                    $(
+                        $index => Self::$Variant($Variant::arbitrary(u)?),
+                    )*
+                    _ => unreachable!(),
+                })
+            }
+        }
+
+        /// Picks a variant, then generates its iterator. Shrinking keeps the variant.
+        #[cfg(feature = "quickcheck")]
+        #[allow(non_snake_case)]
+        impl<$($Variant: quickcheck::Arbitrary,)*> quickcheck::Arbitrary for $AnonIter<$($Variant,)*>
+        {
+            fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+                match g.choose(&[$($index,)*]) {
+                    $(
+                        Some(&$index) => Self::$Variant($Variant::arbitrary(g)),
+                    )*
+                    _ => unreachable!(),
+                }
+            }
+
+            fn shrink(&self) -> alloc::boxed::Box<dyn Iterator<Item = Self>> {
+                match self {
+                    $(
+                        Self::$Variant($Variant) => alloc::boxed::Box::new($Variant.shrink().map(Self::$Variant)),
+                    )*
+                }
+            }
+        }
+
+        #[cfg(feature = "defmt")]
+        #[allow(non_snake_case)]
+        impl<$($Variant: defmt::Format,)*> defmt::Format for $AnonIter<$($Variant,)*>
+        {
+            fn format(&self, f: defmt::Formatter<'_>) {
+                match self {
+                    $(
+                        Self::$Variant($Variant) => defmt::write!(f, "{=str}({})", stringify!($Variant), $Variant),
+                    )*
+                }
+            }
+        }
+
+        create!(@default $AnonIter, $($Variant)*);
+        create!(@rotate $AnonIter $($Variant)*);
+        create!(@select $AnonIter, $($Variant $index)*);
+        create!(@chain $AnonIter $($Variant)*);
+
+        /// Fails to compile unless `self` fits in `N` bytes
+        impl<'a, T: 'a, $($Variant: Iterator<Item = T> + 'a,)* const N: usize> From<$AnonIter<$($Variant,)*>> for AnonIterInline<'a, T, N> {
+            fn from(anon: $AnonIter<$($Variant,)*>) -> Self {
+                Self::new(anon)
+            }
+        }
+
+        impl<'a, T: 'a, $($Variant: Iterator<Item = T> + 'a,)*> From<&'a mut $AnonIter<$($Variant,)*>> for AnonIterRef<'a, T> {
+            fn from(anon: &'a mut $AnonIter<$($Variant,)*>) -> Self {
+                Self::new(anon)
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl<'a, T: 'a, $($Variant: Iterator<Item = T> + Send + 'a,)*> From<$AnonIter<$($Variant,)*>> for AnonIterDyn<'a, T> {
+            fn from(anon: $AnonIter<$($Variant,)*>) -> Self {
+                Self::new(anon)
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl<'a, T: 'a, $($Variant: Iterator<Item = T> + 'a,)*> From<$AnonIter<$($Variant,)*>> for AnonIterDynLocal<'a, T> {
+            fn from(anon: $AnonIter<$($Variant,)*>) -> Self {
+                Self::new(anon)
+            }
+        }
+    };
+}
+
+/// Calls [`create!`] for every arity, from a single table of variants
+///
+/// Each row describes one variant: its name, its 1-based index, its ordinal for the docs,
+/// the names used for it by methods that replace every variant (the new iterator type and
+/// the closure that creates it) and the names of its methods (`unwrap_unchecked`,
+/// `as_unchecked`, `as_mut_unchecked`, `is`, `as`, `as_mut`, `into`, `unwrap`, `expect`, `map`).
+/// An enum over all the variants so far is created at every row ending in
+/// `=> count AnonN AnonIterN AnonIterIntoN AnonIterHeteroN AnonItemN AnonTryIterN AnonErrN arityN`
+/// (the enum, its aliases and its module), along with `From` and `TryFrom` impls converting between it and
+/// each smaller enum.
+macro_rules! create_all {
+    (@acc $rows:tt $enums:tt) => {};
+    (@acc [$($acc:tt)*] [$($enums:tt)*] $(#[$attr:meta])* $Variant:ident = $index:literal: $n:literal $mapped:tt $names:tt => $count:literal $AnonIter:ident $Alias:ident $Into:ident $Hetero:ident $Item:ident $Try:ident $Err:ident $arity:ident, $($rest:tt)*) => {
+        #[doc = concat!("[`", stringify!($AnonIter), "`], its aliases and their trait impls, re-exported at the crate root")]
+        $(#[$attr])*
+        pub mod $arity {
+            use super::*;
+
+            create!($count, $AnonIter $Alias $Into $Hetero $Item $Try $Err, $($acc)* $Variant = $index: $n $mapped $names);
+            create!(@widen [$($enums)*] $AnonIter [$($acc)* $Variant = $index: $n $mapped $names]);
+        }
+        $(#[$attr])*
+        #[doc(inline)]
+        pub use $arity::{$Alias, $AnonIter, $Err, $Hetero, $Into, $Item, $Try};
+        create_all!(
+            @acc [$($acc)* $Variant = $index: $n $mapped $names]
+            [$($enums)* $AnonIter [$($acc)* $Variant = $index: $n $mapped $names]]
+            $($rest)*
+        );
+    };
+    (@acc [$($acc:tt)*] $enums:tt $Variant:ident = $index:literal: $n:literal $mapped:tt $names:tt, $($rest:tt)*) => {
+        create_all!(@acc [$($acc)* $Variant = $index: $n $mapped $names] $enums $($rest)*);
+    };
+    ($($rows:tt)*) => {
+        create_all!(@acc [] [] $($rows)*);
+    };
+}
+
+create_all! {
+    I1 = 1: "1st" (J1 f1) [unwrap_i1_unchecked as_i1_unchecked as_i1_mut_unchecked is_i1 as_i1 as_i1_mut into_i1 unwrap_i1 expect_i1 map_i1] => 1 Anon1 AnonIter1 AnonIterInto1 AnonIterHetero1 AnonItem1 AnonTryIter1 AnonErr1 arity1,
+    I2 = 2: "2nd" (J2 f2) [unwrap_i2_unchecked as_i2_unchecked as_i2_mut_unchecked is_i2 as_i2 as_i2_mut into_i2 unwrap_i2 expect_i2 map_i2] => 2 Anon2 AnonIter2 AnonIterInto2 AnonIterHetero2 AnonItem2 AnonTryIter2 AnonErr2 arity2,
+    I3 = 3: "3rd" (J3 f3) [unwrap_i3_unchecked as_i3_unchecked as_i3_mut_unchecked is_i3 as_i3 as_i3_mut into_i3 unwrap_i3 expect_i3 map_i3] => 3 Anon3 AnonIter3 AnonIterInto3 AnonIterHetero3 AnonItem3 AnonTryIter3 AnonErr3 arity3,
+    #[cfg(feature = "arity-4")]
+    I4 = 4: "4th" (J4 f4) [unwrap_i4_unchecked as_i4_unchecked as_i4_mut_unchecked is_i4 as_i4 as_i4_mut into_i4 unwrap_i4 expect_i4 map_i4] => 4 Anon4 AnonIter4 AnonIterInto4 AnonIterHetero4 AnonItem4 AnonTryIter4 AnonErr4 arity4,
+    #[cfg(feature = "arity-8")]
+    I5 = 5: "5th" (J5 f5) [unwrap_i5_unchecked as_i5_unchecked as_i5_mut_unchecked is_i5 as_i5 as_i5_mut into_i5 unwrap_i5 expect_i5 map_i5] => 5 Anon5 AnonIter5 AnonIterInto5 AnonIterHetero5 AnonItem5 AnonTryIter5 AnonErr5 arity5,
+    #[cfg(feature = "arity-8")]
+    I6 = 6: "6th" (J6 f6) [unwrap_i6_unchecked as_i6_unchecked as_i6_mut_unchecked is_i6 as_i6 as_i6_mut into_i6 unwrap_i6 expect_i6 map_i6] => 6 Anon6 AnonIter6 AnonIterInto6 AnonIterHetero6 AnonItem6 AnonTryIter6 AnonErr6 arity6,
+    #[cfg(feature = "arity-8")]
+    I7 = 7: "7th" (J7 f7) [unwrap_i7_unchecked as_i7_unchecked as_i7_mut_unchecked is_i7 as_i7 as_i7_mut into_i7 unwrap_i7 expect_i7 map_i7] => 7 Anon7 AnonIter7 AnonIterInto7 AnonIterHetero7 AnonItem7 AnonTryIter7 AnonErr7 arity7,
+    #[cfg(feature = "arity-8")]
+    I8 = 8: "8th" (J8 f8) [unwrap_i8_unchecked as_i8_unchecked as_i8_mut_unchecked is_i8 as_i8 as_i8_mut into_i8 unwrap_i8 expect_i8 map_i8] => 8 Anon8 AnonIter8 AnonIterInto8 AnonIterHetero8 AnonItem8 AnonTryIter8 AnonErr8 arity8,
+    #[cfg(feature = "arity-12")]
+    I9 = 9: "9th" (J9 f9) [unwrap_i9_unchecked as_i9_unchecked as_i9_mut_unchecked is_i9 as_i9 as_i9_mut into_i9 unwrap_i9 expect_i9 map_i9] => 9 Anon9 AnonIter9 AnonIterInto9 AnonIterHetero9 AnonItem9 AnonTryIter9 AnonErr9 arity9,
+    #[cfg(feature = "arity-12")]
+    I10 = 10: "10th" (J10 f10) [unwrap_i10_unchecked as_i10_unchecked as_i10_mut_unchecked is_i10 as_i10 as_i10_mut into_i10 unwrap_i10 expect_i10 map_i10] => 10 Anon10 AnonIter10 AnonIterInto10 AnonIterHetero10 AnonItem10 AnonTryIter10 AnonErr10 arity10,
+    #[cfg(feature = "arity-12")]
+    I11 = 11: "11th" (J11 f11) [unwrap_i11_unchecked as_i11_unchecked as_i11_mut_unchecked is_i11 as_i11 as_i11_mut into_i11 unwrap_i11 expect_i11 map_i11] => 11 Anon11 AnonIter11 AnonIterInto11 AnonIterHetero11 AnonItem11 AnonTryIter11 AnonErr11 arity11,
+    #[cfg(feature = "arity-12")]
+    I12 = 12: "12th" (J12 f12) [unwrap_i12_unchecked as_i12_unchecked as_i12_mut_unchecked is_i12 as_i12 as_i12_mut into_i12 unwrap_i12 expect_i12 map_i12] => 12 Anon12 AnonIter12 AnonIterInto12 AnonIterHetero12 AnonItem12 AnonTryIter12 AnonErr12 arity12,
+    I13 = 13: "13th" (J13 f13) [unwrap_i13_unchecked as_i13_unchecked as_i13_mut_unchecked is_i13 as_i13 as_i13_mut into_i13 unwrap_i13 expect_i13 map_i13],
+    I14 = 14: "14th" (J14 f14) [unwrap_i14_unchecked as_i14_unchecked as_i14_mut_unchecked is_i14 as_i14 as_i14_mut into_i14 unwrap_i14 expect_i14 map_i14],
+    I15 = 15: "15th" (J15 f15) [unwrap_i15_unchecked as_i15_unchecked as_i15_mut_unchecked is_i15 as_i15 as_i15_mut into_i15 unwrap_i15 expect_i15 map_i15],
+    #[cfg(feature = "high-arity")]
+    I16 = 16: "16th" (J16 f16) [unwrap_i16_unchecked as_i16_unchecked as_i16_mut_unchecked is_i16 as_i16 as_i16_mut into_i16 unwrap_i16 expect_i16 map_i16] => 16 Anon16 AnonIter16 AnonIterInto16 AnonIterHetero16 AnonItem16 AnonTryIter16 AnonErr16 arity16,
+    I17 = 17: "17th" (J17 f17) [unwrap_i17_unchecked as_i17_unchecked as_i17_mut_unchecked is_i17 as_i17 as_i17_mut into_i17 unwrap_i17 expect_i17 map_i17],
+    I18 = 18: "18th" (J18 f18) [unwrap_i18_unchecked as_i18_unchecked as_i18_mut_unchecked is_i18 as_i18 as_i18_mut into_i18 unwrap_i18 expect_i18 map_i18],
+    I19 = 19: "19th" (J19 f19) [unwrap_i19_unchecked as_i19_unchecked as_i19_mut_unchecked is_i19 as_i19 as_i19_mut into_i19 unwrap_i19 expect_i19 map_i19],
+    I20 = 20: "20th" (J20 f20) [unwrap_i20_unchecked as_i20_unchecked as_i20_mut_unchecked is_i20 as_i20 as_i20_mut into_i20 unwrap_i20 expect_i20 map_i20],
+    I21 = 21: "21st" (J21 f21) [unwrap_i21_unchecked as_i21_unchecked as_i21_mut_unchecked is_i21 as_i21 as_i21_mut into_i21 unwrap_i21 expect_i21 map_i21],
+    I22 = 22: "22nd" (J22 f22) [unwrap_i22_unchecked as_i22_unchecked as_i22_mut_unchecked is_i22 as_i22 as_i22_mut into_i22 unwrap_i22 expect_i22 map_i22],
+    I23 = 23: "23rd" (J23 f23) [unwrap_i23_unchecked as_i23_unchecked as_i23_mut_unchecked is_i23 as_i23 as_i23_mut into_i23 unwrap_i23 expect_i23 map_i23],
+    #[cfg(feature = "high-arity")]
+    I24 = 24: "24th" (J24 f24) [unwrap_i24_unchecked as_i24_unchecked as_i24_mut_unchecked is_i24 as_i24 as_i24_mut into_i24 unwrap_i24 expect_i24 map_i24] => 24 Anon24 AnonIter24 AnonIterInto24 AnonIterHetero24 AnonItem24 AnonTryIter24 AnonErr24 arity24,
+    I25 = 25: "25th" (J25 f25) [unwrap_i25_unchecked as_i25_unchecked as_i25_mut_unchecked is_i25 as_i25 as_i25_mut into_i25 unwrap_i25 expect_i25 map_i25],
+    I26 = 26: "26th" (J26 f26) [unwrap_i26_unchecked as_i26_unchecked as_i26_mut_unchecked is_i26 as_i26 as_i26_mut into_i26 unwrap_i26 expect_i26 map_i26],
+    I27 = 27: "27th" (J27 f27) [unwrap_i27_unchecked as_i27_unchecked as_i27_mut_unchecked is_i27 as_i27 as_i27_mut into_i27 unwrap_i27 expect_i27 map_i27],
+    I28 = 28: "28th" (J28 f28) [unwrap_i28_unchecked as_i28_unchecked as_i28_mut_unchecked is_i28 as_i28 as_i28_mut into_i28 unwrap_i28 expect_i28 map_i28],
+    I29 = 29: "29th" (J29 f29) [unwrap_i29_unchecked as_i29_unchecked as_i29_mut_unchecked is_i29 as_i29 as_i29_mut into_i29 unwrap_i29 expect_i29 map_i29],
+    I30 = 30: "30th" (J30 f30) [unwrap_i30_unchecked as_i30_unchecked as_i30_mut_unchecked is_i30 as_i30 as_i30_mut into_i30 unwrap_i30 expect_i30 map_i30],
+    I31 = 31: "31st" (J31 f31) [unwrap_i31_unchecked as_i31_unchecked as_i31_mut_unchecked is_i31 as_i31 as_i31_mut into_i31 unwrap_i31 expect_i31 map_i31],
+    #[cfg(feature = "high-arity")]
+    I32 = 32: "32nd" (J32 f32) [unwrap_i32_unchecked as_i32_unchecked as_i32_mut_unchecked is_i32 as_i32 as_i32_mut into_i32 unwrap_i32 expect_i32 map_i32] => 32 Anon32 AnonIter32 AnonIterInto32 AnonIterHetero32 AnonItem32 AnonTryIter32 AnonErr32 arity32,
+}
+
+/// Wraps any [`Iterator`] in a variant of an enum, without spelling out the enum's path
+///
+/// The types of the other variants are inferred, usually from the other branches:
+///
+/// ```rust
+/// use anon_iter::IteratorExt as _;
+///
+/// fn foo(x: i32) -> impl Iterator<Item = i32> {
+///     match x {
+///         0 => (1..10).anon_i1_of_3(),
+///         1 => core::iter::once(5).anon_i2_of_3(),
+///         _ => vec![5, 10].into_iter().anon_i3_of_3(),
+///     }
+/// }
+/// ```
+pub trait IteratorExt: Iterator + Sized {
+    create!(@ext AnonIter1; ; I1 anon_i1_of_1);
+    create!(@ext AnonIter2; ; I1 anon_i1_of_2, I2 anon_i2_of_2);
+    create!(@ext AnonIter3; ; I1 anon_i1_of_3, I2 anon_i2_of_3, I3 anon_i3_of_3);
+    #[cfg(feature = "arity-4")]
+    create!(@ext AnonIter4; ; I1 anon_i1_of_4, I2 anon_i2_of_4, I3 anon_i3_of_4, I4 anon_i4_of_4);
+    #[cfg(feature = "arity-8")]
+    create!(@ext AnonIter5; ; I1 anon_i1_of_5, I2 anon_i2_of_5, I3 anon_i3_of_5, I4 anon_i4_of_5, I5 anon_i5_of_5);
+    #[cfg(feature = "arity-8")]
+    create!(@ext AnonIter6; ; I1 anon_i1_of_6, I2 anon_i2_of_6, I3 anon_i3_of_6, I4 anon_i4_of_6, I5 anon_i5_of_6, I6 anon_i6_of_6);
+    #[cfg(feature = "arity-8")]
+    create!(@ext AnonIter7; ; I1 anon_i1_of_7, I2 anon_i2_of_7, I3 anon_i3_of_7, I4 anon_i4_of_7, I5 anon_i5_of_7, I6 anon_i6_of_7, I7 anon_i7_of_7);
+    #[cfg(feature = "arity-8")]
+    create!(@ext AnonIter8; ; I1 anon_i1_of_8, I2 anon_i2_of_8, I3 anon_i3_of_8, I4 anon_i4_of_8, I5 anon_i5_of_8, I6 anon_i6_of_8, I7 anon_i7_of_8, I8 anon_i8_of_8);
+    #[cfg(feature = "arity-12")]
+    create!(@ext AnonIter9; ; I1 anon_i1_of_9, I2 anon_i2_of_9, I3 anon_i3_of_9, I4 anon_i4_of_9, I5 anon_i5_of_9, I6 anon_i6_of_9, I7 anon_i7_of_9, I8 anon_i8_of_9, I9 anon_i9_of_9);
+    #[cfg(feature = "arity-12")]
+    create!(@ext AnonIter10; ; I1 anon_i1_of_10, I2 anon_i2_of_10, I3 anon_i3_of_10, I4 anon_i4_of_10, I5 anon_i5_of_10, I6 anon_i6_of_10, I7 anon_i7_of_10, I8 anon_i8_of_10, I9 anon_i9_of_10, I10 anon_i10_of_10);
+    #[cfg(feature = "arity-12")]
+    create!(@ext AnonIter11; ; I1 anon_i1_of_11, I2 anon_i2_of_11, I3 anon_i3_of_11, I4 anon_i4_of_11, I5 anon_i5_of_11, I6 anon_i6_of_11, I7 anon_i7_of_11, I8 anon_i8_of_11, I9 anon_i9_of_11, I10 anon_i10_of_11, I11 anon_i11_of_11);
+    #[cfg(feature = "arity-12")]
+    create!(@ext AnonIter12; ; I1 anon_i1_of_12, I2 anon_i2_of_12, I3 anon_i3_of_12, I4 anon_i4_of_12, I5 anon_i5_of_12, I6 anon_i6_of_12, I7 anon_i7_of_12, I8 anon_i8_of_12, I9 anon_i9_of_12, I10 anon_i10_of_12, I11 anon_i11_of_12, I12 anon_i12_of_12);
+    #[cfg(feature = "high-arity")]
+    create!(@ext AnonIter16; ; I1 anon_i1_of_16, I2 anon_i2_of_16, I3 anon_i3_of_16, I4 anon_i4_of_16, I5 anon_i5_of_16, I6 anon_i6_of_16, I7 anon_i7_of_16, I8 anon_i8_of_16, I9 anon_i9_of_16, I10 anon_i10_of_16, I11 anon_i11_of_16, I12 anon_i12_of_16, I13 anon_i13_of_16, I14 anon_i14_of_16, I15 anon_i15_of_16, I16 anon_i16_of_16);
+    #[cfg(feature = "high-arity")]
+    create!(@ext AnonIter24; ; I1 anon_i1_of_24, I2 anon_i2_of_24, I3 anon_i3_of_24, I4 anon_i4_of_24, I5 anon_i5_of_24, I6 anon_i6_of_24, I7 anon_i7_of_24, I8 anon_i8_of_24, I9 anon_i9_of_24, I10 anon_i10_of_24, I11 anon_i11_of_24, I12 anon_i12_of_24, I13 anon_i13_of_24, I14 anon_i14_of_24, I15 anon_i15_of_24, I16 anon_i16_of_24, I17 anon_i17_of_24, I18 anon_i18_of_24, I19 anon_i19_of_24, I20 anon_i20_of_24, I21 anon_i21_of_24, I22 anon_i22_of_24, I23 anon_i23_of_24, I24 anon_i24_of_24);
+    #[cfg(feature = "high-arity")]
+    create!(@ext AnonIter32; ; I1 anon_i1_of_32, I2 anon_i2_of_32, I3 anon_i3_of_32, I4 anon_i4_of_32, I5 anon_i5_of_32, I6 anon_i6_of_32, I7 anon_i7_of_32, I8 anon_i8_of_32, I9 anon_i9_of_32, I10 anon_i10_of_32, I11 anon_i11_of_32, I12 anon_i12_of_32, I13 anon_i13_of_32, I14 anon_i14_of_32, I15 anon_i15_of_32, I16 anon_i16_of_32, I17 anon_i17_of_32, I18 anon_i18_of_32, I19 anon_i19_of_32, I20 anon_i20_of_32, I21 anon_i21_of_32, I22 anon_i22_of_32, I23 anon_i23_of_32, I24 anon_i24_of_32, I25 anon_i25_of_32, I26 anon_i26_of_32, I27 anon_i27_of_32, I28 anon_i28_of_32, I29 anon_i29_of_32, I30 anon_i30_of_32, I31 anon_i31_of_32, I32 anon_i32_of_32);
+}
+
+impl<I: Iterator> IteratorExt for I {}
+
+create!(@of of1 AnonIter1, I1 i1);
+create!(@of of2 AnonIter2, I1 i1, I2 i2);
+create!(@of of3 AnonIter3, I1 i1, I2 i2, I3 i3);
+#[cfg(feature = "arity-4")]
+create!(@of of4 AnonIter4, I1 i1, I2 i2, I3 i3, I4 i4);
+#[cfg(feature = "arity-8")]
+create!(@of of5 AnonIter5, I1 i1, I2 i2, I3 i3, I4 i4, I5 i5);
+#[cfg(feature = "arity-8")]
+create!(@of of6 AnonIter6, I1 i1, I2 i2, I3 i3, I4 i4, I5 i5, I6 i6);
+#[cfg(feature = "arity-8")]
+create!(@of of7 AnonIter7, I1 i1, I2 i2, I3 i3, I4 i4, I5 i5, I6 i6, I7 i7);
+#[cfg(feature = "arity-8")]
+create!(@of of8 AnonIter8, I1 i1, I2 i2, I3 i3, I4 i4, I5 i5, I6 i6, I7 i7, I8 i8);
+#[cfg(feature = "arity-12")]
+create!(@of of9 AnonIter9, I1 i1, I2 i2, I3 i3, I4 i4, I5 i5, I6 i6, I7 i7, I8 i8, I9 i9);
+#[cfg(feature = "arity-12")]
+create!(@of of10 AnonIter10, I1 i1, I2 i2, I3 i3, I4 i4, I5 i5, I6 i6, I7 i7, I8 i8, I9 i9, I10 i10);
+#[cfg(feature = "arity-12")]
+create!(@of of11 AnonIter11, I1 i1, I2 i2, I3 i3, I4 i4, I5 i5, I6 i6, I7 i7, I8 i8, I9 i9, I10 i10, I11 i11);
+#[cfg(feature = "arity-12")]
+create!(@of of12 AnonIter12, I1 i1, I2 i2, I3 i3, I4 i4, I5 i5, I6 i6, I7 i7, I8 i8, I9 i9, I10 i10, I11 i11, I12 i12);
+#[cfg(feature = "high-arity")]
+create!(@of of16 AnonIter16, I1 i1, I2 i2, I3 i3, I4 i4, I5 i5, I6 i6, I7 i7, I8 i8, I9 i9, I10 i10, I11 i11, I12 i12, I13 i13, I14 i14, I15 i15, I16 i16);
+#[cfg(feature = "high-arity")]
+create!(@of of24 AnonIter24, I1 i1, I2 i2, I3 i3, I4 i4, I5 i5, I6 i6, I7 i7, I8 i8, I9 i9, I10 i10, I11 i11, I12 i12, I13 i13, I14 i14, I15 i15, I16 i16, I17 i17, I18 i18, I19 i19, I20 i20, I21 i21, I22 i22, I23 i23, I24 i24);
+#[cfg(feature = "high-arity")]
+create!(@of of32 AnonIter32, I1 i1, I2 i2, I3 i3, I4 i4, I5 i5, I6 i6, I7 i7, I8 i8, I9 i9, I10 i10, I11 i11, I12 i12, I13 i13, I14 i14, I15 i15, I16 i16, I17 i17, I18 i18, I19 i19, I20 i20, I21 i21, I22 i22, I23 i23, I24 i24, I25 i25, I26 i26, I27 i27, I28 i28, I29 i29, I30 i30, I31 i31, I32 i32);
+
+/// Short for [`AnonIter1`]
+pub type A1<I1> = AnonIter1<I1>;
+
+/// Short for [`AnonIter2`]
+pub type A2<I1, I2> = AnonIter2<I1, I2>;
+
+/// Short for [`AnonIter3`]
+pub type A3<I1, I2, I3> = AnonIter3<I1, I2, I3>;
+
+/// Short for [`AnonIter4`]
+#[cfg(feature = "arity-4")]
+pub type A4<I1, I2, I3, I4> = AnonIter4<I1, I2, I3, I4>;
+
+/// Short for [`AnonIter5`]
+#[cfg(feature = "arity-8")]
+pub type A5<I1, I2, I3, I4, I5> = AnonIter5<I1, I2, I3, I4, I5>;
+
+/// Short for [`AnonIter6`]
+#[cfg(feature = "arity-8")]
+pub type A6<I1, I2, I3, I4, I5, I6> = AnonIter6<I1, I2, I3, I4, I5, I6>;
+
+/// Short for [`AnonIter7`]
+#[cfg(feature = "arity-8")]
+pub type A7<I1, I2, I3, I4, I5, I6, I7> = AnonIter7<I1, I2, I3, I4, I5, I6, I7>;
+
+/// Short for [`AnonIter8`]
+#[cfg(feature = "arity-8")]
+pub type A8<I1, I2, I3, I4, I5, I6, I7, I8> = AnonIter8<I1, I2, I3, I4, I5, I6, I7, I8>;
+
+/// Short for [`AnonIter9`]
+#[cfg(feature = "arity-12")]
+pub type A9<I1, I2, I3, I4, I5, I6, I7, I8, I9> = AnonIter9<I1, I2, I3, I4, I5, I6, I7, I8, I9>;
+
+/// Short for [`AnonIter10`]
+#[cfg(feature = "arity-12")]
+pub type A10<I1, I2, I3, I4, I5, I6, I7, I8, I9, I10> =
+    AnonIter10<I1, I2, I3, I4, I5, I6, I7, I8, I9, I10>;
+
+/// Short for [`AnonIter11`]
+#[cfg(feature = "arity-12")]
+pub type A11<I1, I2, I3, I4, I5, I6, I7, I8, I9, I10, I11> =
+    AnonIter11<I1, I2, I3, I4, I5, I6, I7, I8, I9, I10, I11>;
+
+/// Short for [`AnonIter12`]
+#[cfg(feature = "arity-12")]
+pub type A12<I1, I2, I3, I4, I5, I6, I7, I8, I9, I10, I11, I12> =
+    AnonIter12<I1, I2, I3, I4, I5, I6, I7, I8, I9, I10, I11, I12>;
+
+/// Short for [`AnonIter16`]
+#[cfg(feature = "high-arity")]
+pub type A16<I1, I2, I3, I4, I5, I6, I7, I8, I9, I10, I11, I12, I13, I14, I15, I16> =
+    AnonIter16<I1, I2, I3, I4, I5, I6, I7, I8, I9, I10, I11, I12, I13, I14, I15, I16>;
+
+/// Short for [`AnonIter24`]
+#[cfg(feature = "high-arity")]
+pub type A24<
+    I1,
+    I2,
+    I3,
+    I4,
+    I5,
+    I6,
+    I7,
+    I8,
+    I9,
+    I10,
+    I11,
+    I12,
+    I13,
+    I14,
+    I15,
+    I16,
+    I17,
+    I18,
+    I19,
+    I20,
+    I21,
+    I22,
+    I23,
+    I24,
+> = AnonIter24<
+    I1,
+    I2,
+    I3,
+    I4,
+    I5,
+    I6,
+    I7,
+    I8,
+    I9,
+    I10,
+    I11,
+    I12,
+    I13,
+    I14,
+    I15,
+    I16,
+    I17,
+    I18,
+    I19,
+    I20,
+    I21,
+    I22,
+    I23,
+    I24,
+>;
+
+/// Short for [`AnonIter32`]
+#[cfg(feature = "high-arity")]
+pub type A32<
+    I1,
+    I2,
+    I3,
+    I4,
+    I5,
+    I6,
+    I7,
+    I8,
+    I9,
+    I10,
+    I11,
+    I12,
+    I13,
+    I14,
+    I15,
+    I16,
+    I17,
+    I18,
+    I19,
+    I20,
+    I21,
+    I22,
+    I23,
+    I24,
+    I25,
+    I26,
+    I27,
+    I28,
+    I29,
+    I30,
+    I31,
+    I32,
+> = AnonIter32<
+    I1,
+    I2,
+    I3,
+    I4,
+    I5,
+    I6,
+    I7,
+    I8,
+    I9,
+    I10,
+    I11,
+    I12,
+    I13,
+    I14,
+    I15,
+    I16,
+    I17,
+    I18,
+    I19,
+    I20,
+    I21,
+    I22,
+    I23,
+    I24,
+    I25,
+    I26,
+    I27,
+    I28,
+    I29,
+    I30,
+    I31,
+    I32,
+>;
+
+/// Wraps each arm of a `match` in the variant of the enum with as many variants as there are arms
+///
+/// The 1st arm is wrapped in `I1`, the 2nd in `I2` and so on, so adding or removing an arm
+/// needs no variant bookkeeping. Arms whose body is a block may leave out the trailing comma.
+/// With `default-features = false`, a `match` with more than 3 arms needs the feature of its enum,
+/// such as `arity-8` for 5 to 8 arms.
+///
+/// ```rust
+/// use anon_iter::anon;
+///
+/// fn foo(x: i32) -> impl Iterator<Item = i32> {
+///     anon!(match x {
+///         0 => 1..10,
+///         1 | 2 => vec![5, 10].into_iter(),
+///         _ => core::iter::empty(),
+///     })
+/// }
+/// ```
+#[macro_export]
+macro_rules! anon {
+    (match $($tokens:tt)+) => {
+        $crate::anon!(@scrutinee [] $($tokens)+)
+    };
+    // The scrutinee is every token before the braces holding the arms
+    (@scrutinee [$($scrutinee:tt)+] { $($arms:tt)* }) => {
+        $crate::anon!(@arms ($($scrutinee)+) [] _ [(I1 AnonIter1) (I2 AnonIter2) (I3 AnonIter3) (I4 AnonIter4) (I5 AnonIter5) (I6 AnonIter6) (I7 AnonIter7) (I8 AnonIter8) (I9 AnonIter9) (I10 AnonIter10) (I11 AnonIter11) (I12 AnonIter12)] $($arms)*)
+    };
+    (@scrutinee [$($scrutinee:tt)*] $token:tt $($tokens:tt)+) => {
+        $crate::anon!(@scrutinee [$($scrutinee)* $token] $($tokens)+)
+    };
+    (@arms $scrutinee:tt [] $AnonIter:tt $variants:tt) => {
+        compile_error!("`anon!` needs at least 1 arm")
+    };
+    (@arms ($scrutinee:expr) [$(({$($head:tt)*} $Variant:ident $body:expr))*] $AnonIter:ident $variants:tt) => {
+        $crate::__anon_arity!($AnonIter {
+            match $scrutinee {
+                $(
+                    $($head)* => $crate::$AnonIter::$Variant($body),
+                )*
+            }
+        })
+    };
+    (@arms $scrutinee:tt $acc:tt $AnonIter:tt [] $($arms:tt)+) => {
+        compile_error!("`anon!` supports at most 12 arms")
+    };
+    (@arms $scrutinee:tt [$($acc:tt)*] $_AnonIter:tt [($Variant:ident $AnonIter:ident) $($variants:tt)*] $($pat:pat)|+ $(if $guard:expr)? => $body:expr $(, $($arms:tt)*)?) => {
+        $crate::anon!(@arms $scrutinee [$($acc)* ({$($pat)|+ $(if $guard)?} $Variant $body)] $AnonIter [$($variants)*] $($($arms)*)?)
+    };
+    (@arms $scrutinee:tt [$($acc:tt)*] $_AnonIter:tt [($Variant:ident $AnonIter:ident) $($variants:tt)*] $($pat:pat)|+ $(if $guard:expr)? => $body:block $($arms:tt)*) => {
+        $crate::anon!(@arms $scrutinee [$($acc)* ({$($pat)|+ $(if $guard)?} $Variant $body)] $AnonIter [$($variants)*] $($arms)*)
+    };
+}
+
+/// Expands to `body` if the enum `AnonIterN` is enabled, and to an error naming the feature that
+/// enables it otherwise. Each of these macros checks the enums of one feature, then passes `body` on.
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "arity-4")]
+macro_rules! __anon_arity {
+    ($AnonIter:ident $body:tt) => {
+        $crate::__anon_arity_8!($AnonIter $body)
+    };
+}
+
+/// Expands to `body` if the enum `AnonIterN` is enabled, and to an error naming the feature that
+/// enables it otherwise. Each of these macros checks the enums of one feature, then passes `body` on.
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "arity-4"))]
+macro_rules! __anon_arity {
+    (AnonIter4 $body:tt) => {
+        compile_error!("`AnonIter4` requires the `arity-4` feature of `anon_iter`")
+    };
+    ($AnonIter:ident $body:tt) => {
+        $crate::__anon_arity_8!($AnonIter $body)
+    };
+}
+
+/// Part of `__anon_arity!`, checking the enums of the `arity-8` feature
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "arity-8")]
+macro_rules! __anon_arity_8 {
+    ($AnonIter:ident $body:tt) => {
+        $crate::__anon_arity_12!($AnonIter $body)
+    };
+}
+
+/// Part of `__anon_arity!`, checking the enums of the `arity-8` feature
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "arity-8"))]
+macro_rules! __anon_arity_8 {
+    (AnonIter5 $body:tt) => {
+        compile_error!("`AnonIter5` requires the `arity-8` feature of `anon_iter`")
+    };
+    (AnonIter6 $body:tt) => {
+        compile_error!("`AnonIter6` requires the `arity-8` feature of `anon_iter`")
+    };
+    (AnonIter7 $body:tt) => {
+        compile_error!("`AnonIter7` requires the `arity-8` feature of `anon_iter`")
+    };
+    (AnonIter8 $body:tt) => {
+        compile_error!("`AnonIter8` requires the `arity-8` feature of `anon_iter`")
+    };
+    ($AnonIter:ident $body:tt) => {
+        $crate::__anon_arity_12!($AnonIter $body)
+    };
+}
+
+/// Part of `__anon_arity!`, checking the enums of the `arity-12` feature
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "arity-12")]
+macro_rules! __anon_arity_12 {
+    ($AnonIter:ident $body:tt) => {
+        $crate::__anon_arity_high!($AnonIter $body)
+    };
+}
+
+/// Part of `__anon_arity!`, checking the enums of the `arity-12` feature
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "arity-12"))]
+macro_rules! __anon_arity_12 {
+    (AnonIter9 $body:tt) => {
+        compile_error!("`AnonIter9` requires the `arity-12` feature of `anon_iter`")
+    };
+    (AnonIter10 $body:tt) => {
+        compile_error!("`AnonIter10` requires the `arity-12` feature of `anon_iter`")
+    };
+    (AnonIter11 $body:tt) => {
+        compile_error!("`AnonIter11` requires the `arity-12` feature of `anon_iter`")
+    };
+    (AnonIter12 $body:tt) => {
+        compile_error!("`AnonIter12` requires the `arity-12` feature of `anon_iter`")
+    };
+    ($AnonIter:ident $body:tt) => {
+        $crate::__anon_arity_high!($AnonIter $body)
+    };
+}
+
+/// Part of `__anon_arity!`, checking the enums of the `high-arity` feature
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "high-arity")]
+macro_rules! __anon_arity_high {
+    ($AnonIter:ident $body:tt) => {
+        $body
+    };
+}
+
+/// Part of `__anon_arity!`, checking the enums of the `high-arity` feature
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "high-arity"))]
+macro_rules! __anon_arity_high {
+    (AnonIter16 $body:tt) => {
+        compile_error!("`AnonIter16` requires the `high-arity` feature of `anon_iter`")
+    };
+    (AnonIter24 $body:tt) => {
+        compile_error!("`AnonIter24` requires the `high-arity` feature of `anon_iter`")
+    };
+    (AnonIter32 $body:tt) => {
+        compile_error!("`AnonIter32` requires the `high-arity` feature of `anon_iter`")
+    };
+    ($AnonIter:ident $body:tt) => {
+        $body
+    };
+}
+
+/// Procedural macros, enabled by the `macros` feature
+///
+/// [`#[anon]`](macros::anon) wraps every value a function returns in a variant of the enum with as
+/// many variants as there are values, like [`anon!`](crate::anon!) does for the arms of a `match`.
+/// The values are the operands of `return` and the tail expression, and the branches of
+/// a tail `match` or `if` are looked into:
+///
+/// ```rust
+/// use anon_iter::macros::anon;
+///
+/// #[anon]
+/// fn foo(x: i32) -> impl Iterator<Item = i32> {
+///     if x < 0 {
+///         return core::iter::empty();
+///     }
+///     match x {
+///         0 => 1..10,
+///         _ => vec![5, 10].into_iter(),
+///     }
+/// }
+///
+/// assert_eq!(foo(-1).count(), 0);
+/// ```
+///
+/// With the `high-arity` feature, more than 12 values go in the smallest of `AnonIter16`,
+/// `AnonIter24` and `AnonIter32` that fits them, with [`Empty`] in the variants
+/// left over. Returning from more than 32 places is an error, as is needing an enum whose feature
+/// is disabled. `panic!`, `unreachable!`, `todo!` and `unimplemented!` branches are left alone,
+/// as are closures and nested functions.
+///
+/// [`#[derive(IteratorEnum)]`](macros::IteratorEnum) implements the iterator traits of the enums of
+/// this crate for an enum of your own, so its variants can have meaningful names.
+/// Every variant must wrap a single iterator, and all of them must yield the same items:
+///
+/// ```rust
+/// use anon_iter::macros::IteratorEnum;
+///
+/// #[derive(IteratorEnum)]
+/// enum Rows<'a> {
+///     Cached(core::iter::Copied<core::slice::Iter<'a, u32>>),
+///     Computed(core::ops::Range<u32>),
+/// }
+///
+/// fn rows(cache: &[u32]) -> Rows<'_> {
+///     if cache.is_empty() {
+///         Rows::Computed(0..3)
+///     } else {
+///         Rows::Cached(cache.iter().copied())
+///     }
+/// }
+///
+/// assert_eq!(rows(&[]).count(), 3);
+/// ```
+///
+/// `Iterator`, `DoubleEndedIterator`, `ExactSizeIterator` and `FusedIterator` are each
+/// implemented when every variant implements them.
+///
+/// To pick the traits instead, list them in `#[anon(...)]` on the enum. Besides the iterator
+/// traits, any other trait the enums of this crate forward can be listed: the formatting traits
+/// (`Display`, `Binary`, `Octal`, `LowerHex`, `UpperHex`, `LowerExp` and `UpperExp`), `Error`,
+/// `Future`, and `Read`, `BufRead` and `Write` from `std::io`. Those don't need the variants to be
+/// iterators. An enum forwarding `Future` pins its variants, so it can't implement `Drop`, and
+/// it is `Unpin` exactly when they all are:
+///
+/// ```rust
+/// use anon_iter::macros::anon;
+///
+/// #[anon(Display, LowerHex)]
+/// enum Id {
+///     Small(u8),
+///     Large(u64),
+/// }
+///
+/// assert_eq!(format!("{} {:x}", Id::Small(10), Id::Large(255)), "10 ff");
+/// ```
+///
+/// [`match_iters!`](macros::match_iters) wraps the arms of a `match` like [`anon!`](crate::anon!),
+/// but in an enum it defines just for that `match`. There is no limit on the number of arms,
+/// and no enum of this crate to pick. Arms that never produce a value, such as `panic!()`,
+/// get no variant:
+///
+/// ```rust
+/// use anon_iter::macros::match_iters;
+///
+/// fn foo(x: i32) -> impl DoubleEndedIterator<Item = i32> {
+///     match_iters!(match x {
+///         0 => 1..10,
+///         1 | 2 => vec![5, 10].into_iter(),
+///         i32::MIN => unreachable!(),
+///         _ => core::iter::once(x),
+///     })
+/// }
+///
+/// assert_eq!(foo(1).rev().collect::<Vec<_>>(), [10, 5]);
+/// ```
+#[cfg(feature = "macros")]
+pub mod macros {
+    pub use anon_iter_macros::{anon, match_iters, IteratorEnum};
+}
+
+/// Everything needed to wrap iterators: the enums, their short aliases, [`IteratorExt`] and [`anon!`]
+///
+/// ```rust
+/// use anon_iter::prelude::*;
+///
+/// fn foo(x: i32) -> impl Iterator<Item = i32> {
+///     match x {
+///         0 => A3::I1(1..10),
+///         1 => core::iter::once(5).anon_i2_of_3(),
+///         _ => AnonIter3::I3(vec![5, 10].into_iter()),
+///     }
+/// }
+/// ```
+pub mod prelude {
+    pub use crate::{anon, IteratorExt};
+    pub use crate::{Anon1, Anon2, Anon3, AnonIter1, AnonIter2, AnonIter3, A1, A2, A3};
+    #[cfg(feature = "arity-12")]
+    pub use crate::{
+        Anon10, Anon11, Anon12, Anon9, AnonIter10, AnonIter11, AnonIter12, AnonIter9, A10, A11,
+        A12, A9,
+    };
+    #[cfg(feature = "high-arity")]
+    pub use crate::{Anon16, Anon24, Anon32, AnonIter16, AnonIter24, AnonIter32, A16, A24, A32};
+    #[cfg(feature = "arity-4")]
+    pub use crate::{Anon4, AnonIter4, A4};
+    #[cfg(feature = "arity-8")]
+    pub use crate::{
+        Anon5, Anon6, Anon7, Anon8, AnonIter5, AnonIter6, AnonIter7, AnonIter8, A5, A6, A7, A8,
+    };
+}
+
+/// Wraps one of any number of `impl Iterator`s, by nesting itself in [`Tail`](Self::Tail)
+///
+/// `AnonChain<I1, AnonChain<I2, I3>>` holds one of 3 iterators, like [`AnonIter3`] does,
+/// but there is no limit on how deep the chain goes. This is meant for code generators,
+/// which would otherwise run out of variants. [`inject`](Self::inject) puts an iterator
+/// in the right place without spelling out the nesting:
+///
+/// ```rust
+/// use anon_iter::AnonChain;
+/// use core::iter::{empty, once, Empty, Once};
+/// use core::ops::Range;
+///
+/// type Chain = AnonChain<Range<u32>, AnonChain<Once<u32>, Empty<u32>>>;
+///
+/// let it: Chain = AnonChain::inject(once(3));
+/// assert_eq!(it.collect::<Vec<_>>(), [3]);
+///
+/// // The enums of this crate convert into the chain with as many links
+/// let it: Chain = anon_iter::AnonIter3::I3(empty()).into();
+/// assert_eq!(it.count(), 0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AnonChain<H, T> {
+    /// The first `impl Iterator` of the chain
+    Head(H),
+    /// Any of the others, the last one being the tail itself
+    Tail(T),
+}
+
+impl<H, T> AnonChain<H, T> {
+    /// Wraps `iter` in the link of the chain that holds its type
+    ///
+    /// `Index` is inferred, and only says where that link is. It can't be inferred
+    /// when more than one link holds the same type.
+    pub fn inject<I, Index>(iter: I) -> Self
+    where
+        Self: chain::Inject<I, Index>,
+    {
+        chain::Inject::inject(iter)
+    }
+}
+
+__forward!(Iterator [nightly] [H, T] AnonChain<H, T>, [T: Iterator<Item = H::Item>,], H::Item, Head: H, Tail: T);
+#[cfg(feature = "double-ended")]
+__forward!(DoubleEndedIterator [nightly] [H, T] AnonChain<H, T>, [T: Iterator<Item = H::Item>,], H::Item, Head: H, Tail: T);
+#[cfg(feature = "exact-size")]
+__forward!(ExactSizeIterator [nightly] [H, T] AnonChain<H, T>, [T: Iterator<Item = H::Item>,], H::Item, Head: H, Tail: T);
+#[cfg(feature = "fused")]
+__forward!(FusedIterator [nightly] [H, T] AnonChain<H, T>, [T: Iterator<Item = H::Item>,], H::Item, Head: H, Tail: T);
+#[cfg(feature = "fmt")]
+__forward!(Display [nightly] [H, T] AnonChain<H, T>, [], (), Head: H, Tail: T);
+#[cfg(feature = "fmt")]
+__forward!(Binary [nightly] [H, T] AnonChain<H, T>, [], (), Head: H, Tail: T);
+#[cfg(feature = "fmt")]
+__forward!(Octal [nightly] [H, T] AnonChain<H, T>, [], (), Head: H, Tail: T);
+#[cfg(feature = "fmt")]
+__forward!(LowerHex [nightly] [H, T] AnonChain<H, T>, [], (), Head: H, Tail: T);
+#[cfg(feature = "fmt")]
+__forward!(UpperHex [nightly] [H, T] AnonChain<H, T>, [], (), Head: H, Tail: T);
+#[cfg(feature = "fmt")]
+__forward!(LowerExp [nightly] [H, T] AnonChain<H, T>, [], (), Head: H, Tail: T);
+#[cfg(feature = "fmt")]
+__forward!(UpperExp [nightly] [H, T] AnonChain<H, T>, [], (), Head: H, Tail: T);
+
+/// Finds the link of an [`AnonChain`] that holds an iterator
+pub mod chain {
+    use crate::AnonChain;
+    use core::marker::PhantomData;
+
+    /// The iterator is in the current link
+    pub enum Here {}
+
+    /// The iterator is further down the chain, at `Index` from the next link
+    pub struct There<Index>(PhantomData<Index>);
+
+    /// Wraps `I` in the link of `Self` found at `Index`
+    ///
+    /// Use [`AnonChain::inject`] rather than this trait directly.
+    pub trait Inject<I, Index> {
+        /// Wraps `iter` in the link at `Index`
+        fn inject(iter: I) -> Self;
+    }
+
+    /// The last link of a chain is the iterator itself
+    impl<I> Inject<I, Here> for I {
+        fn inject(iter: I) -> Self {
+            iter
+        }
+    }
+
+    impl<H, T> Inject<H, Here> for AnonChain<H, T> {
+        fn inject(iter: H) -> Self {
+            AnonChain::Head(iter)
+        }
+    }
+
+    impl<I, Index, H, T: Inject<I, Index>> Inject<I, There<Index>> for AnonChain<H, T> {
+        fn inject(iter: I) -> Self {
+            AnonChain::Tail(T::inject(iter))
+        }
+    }
+}
+
+/// The active variant of an enum, returned by `debug_variant`
+///
+/// [`Display`](fmt::Display) prints just the name, e.g. `I2`, and [`Debug`] prints the name and the index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DebugVariant {
+    name: &'static str,
+    index: usize,
+}
+
+impl DebugVariant {
+    /// The variant's name, e.g. `"I2"`
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The variant's 1-based index, e.g. `2` for `I2`
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl fmt::Display for DebugVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name)
+    }
+}
+
+/// Counts the items of an enum and reports them when iteration ends, returned by `inspect_variant`
+#[derive(Debug)]
+pub struct InspectVariant<I, F: FnOnce(DebugVariant, usize)> {
+    iter: I,
+    variant: DebugVariant,
+    count: usize,
+    f: Option<F>,
+}
+
+impl<I, F: FnOnce(DebugVariant, usize)> InspectVariant<I, F> {
+    fn report(&mut self) {
+        if let Some(f) = self.f.take() {
+            f(self.variant, self.count);
+        }
+    }
+}
+
+impl<I: Iterator, F: FnOnce(DebugVariant, usize)> Iterator for InspectVariant<I, F> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next();
+        match item {
+            Some(_) => self.count += 1,
+            None => self.report(),
+        }
+        item
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, F: FnOnce(DebugVariant, usize)> Drop for InspectVariant<I, F> {
+    fn drop(&mut self) {
+        self.report();
+    }
+}
+
+/// Converts the items of every variant of `A` into `T`, for variants that yield different types
+///
+/// Returned by `items_into`, and named `AnonIterIntoN` when it wraps `AnonN`.
+/// It saves calling `.map(Into::into)` in every branch:
+///
+/// ```rust
+/// use anon_iter::AnonIter2;
+///
+/// fn words(owned: bool) -> impl Iterator<Item = String> {
+///     if owned {
+///         AnonIter2::I1(vec![String::from("owned")].into_iter())
+///     } else {
+///         AnonIter2::I2(["borrowed"].iter().copied())
+///     }
+///     .items_into()
+/// }
+///
+/// assert_eq!(words(false).collect::<Vec<_>>(), ["borrowed"]);
+/// ```
+pub struct AnonIterInto<T, A> {
+    anon: A,
+    item: PhantomData<fn() -> T>,
+}
+
+impl<T, A> AnonIterInto<T, A> {
+    /// Converts the items of `anon` into `T`
+    pub fn new(anon: A) -> Self {
+        Self {
+            anon,
+            item: PhantomData,
+        }
+    }
+
+    /// Returns the wrapped enum
+    pub fn into_inner(self) -> A {
+        self.anon
+    }
+}
+
+impl<T, A: Clone> Clone for AnonIterInto<T, A> {
+    fn clone(&self) -> Self {
+        Self::new(self.anon.clone())
+    }
+}
+
+impl<T, A: fmt::Debug> fmt::Debug for AnonIterInto<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("AnonIterInto").field(&self.anon).finish()
+    }
+}
+
+/// Wraps every item of `A` in the variant its iterator is in, for variants that yield
+/// unrelated types
+///
+/// Returned by `anon_items`, and named `AnonIterHeteroN` when it wraps `AnonN`. The items are
+/// `AnonN`s too, also named `AnonItemN`, so callers can match on which kind of item they got:
+///
+/// ```rust
+/// use anon_iter::{AnonItem2, AnonIter2};
+///
+/// fn parse(numbers: bool) -> impl Iterator<Item = AnonItem2<u32, char>> {
+///     if numbers {
+///         AnonIter2::I1(1..3)
+///     } else {
+///         AnonIter2::I2("ab".chars())
+///     }
+///     .anon_items()
+/// }
+///
+/// for item in parse(false) {
+///     match item {
+///         AnonItem2::I1(number) => println!("number {}", number),
+///         AnonItem2::I2(letter) => println!("letter {}", letter),
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct AnonIterHetero<A> {
+    anon: A,
+}
+
+impl<A> AnonIterHetero<A> {
+    /// Wraps the items of `anon` in the variant its iterator is in
+    pub fn new(anon: A) -> Self {
+        Self { anon }
+    }
+
+    /// Returns the wrapped enum
+    pub fn into_inner(self) -> A {
+        self.anon
+    }
+}
+
+/// Wraps the errors of `A` in the variant its iterator is in, for fallible variants whose
+/// error types differ
+///
+/// Returned by `anon_errors`, and named `AnonTryIterN` when it wraps `AnonN`. The errors are
+/// `AnonN`s too, also named `AnonErrN`, and implement [`Error`](core::error::Error) when all of
+/// the wrapped errors do, so that they work with `?` without a `map_err` in every branch:
+///
+/// ```rust
+/// use anon_iter::{AnonErr2, AnonIter2};
+/// use std::convert::TryFrom;
+/// use std::num::{ParseIntError, TryFromIntError};
+///
+/// fn bytes(parse: bool) -> impl Iterator<Item = Result<u8, AnonErr2<ParseIntError, TryFromIntError>>> {
+///     if parse {
+///         AnonIter2::I1(["1", "x"].iter().map(|s| s.parse::<u8>()))
+///     } else {
+///         AnonIter2::I2([300].iter().map(|&n: &u32| u8::try_from(n)))
+///     }
+///     .anon_errors()
+/// }
+///
+/// assert!(matches!(bytes(false).next(), Some(Err(AnonErr2::I2(_)))));
+/// ```
+#[derive(Clone, Debug)]
+pub struct AnonTryIter<A> {
+    anon: A,
+}
+
+impl<A> AnonTryIter<A> {
+    /// Wraps the errors of `anon` in the variant its iterator is in
+    pub fn new(anon: A) -> Self {
+        Self { anon }
+    }
+
+    /// Returns the wrapped enum
+    pub fn into_inner(self) -> A {
+        self.anon
+    }
+}
+
+/// Forwards only `next` and `next_back` to the iterators in `A`, and a few cold methods through a table
+///
+/// Returned by `lite`. `AnonIterLite` keeps `next` statically dispatched, calls `size_hint`,
+/// `nth` and `count` through a small table of function pointers, and leaves the rest of the
+/// [`Iterator`] methods to their `next`-based defaults. The table is only an indirection: each
+/// of its entries is still compiled for every variant, just like the arms of a `match`, so it
+/// makes neither binaries smaller nor builds faster. Methods such as `fold` lose the faster
+/// implementations of the wrapped iterators, so the enum itself remains the better choice in hot loops.
+///
+/// ```rust
+/// use anon_iter::{AnonIter2, AnonIterLite};
+///
+/// fn digits(n: u32) -> AnonIterLite<AnonIter2<core::ops::Range<u32>, core::iter::Once<u32>>> {
+///     if n > 9 { AnonIter2::I1(0..10) } else { AnonIter2::I2(core::iter::once(n)) }.lite()
+/// }
+///
+/// assert_eq!(digits(100).size_hint(), (10, Some(10)));
+/// assert_eq!(digits(100).nth(3), Some(3));
+/// assert_eq!(digits(4).collect::<Vec<_>>(), [4]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct AnonIterLite<A> {
+    anon: A,
+}
+
+impl<A> AnonIterLite<A> {
+    /// Wraps `anon`, dispatching its cold methods through a table
+    pub fn new(anon: A) -> Self {
+        Self { anon }
+    }
+
+    /// Returns the wrapped enum
+    pub fn into_inner(self) -> A {
+        self.anon
+    }
+}
+
+/// The methods of an [`AnonIterLite`] that go through a table, with an entry for every variant
+#[allow(clippy::type_complexity)]
+struct LiteVTable<A, T, const N: usize> {
+    size_hint: [fn(&A) -> (usize, Option<usize>); N],
+    nth: [fn(&mut A, usize) -> Option<T>; N],
+    count: [fn(A) -> usize; N],
+}
+
+/// Defines a boxed `dyn Iterator`, whose trait object has the bounds `$($bound)*`
+macro_rules! create_dyn {
+    ($(#[$attr:meta])* $AnonIterDyn:ident $($bound:tt)*) => {
+        $(#[$attr])*
+        #[cfg(feature = "alloc")]
+        pub struct $AnonIterDyn<'a, T>(Box<dyn Iterator<Item = T> $($bound)* + 'a>);
+
+        #[cfg(feature = "alloc")]
+        impl<'a, T> $AnonIterDyn<'a, T> {
+            /// Boxes `iter`, erasing its type
+            pub fn new<I: Iterator<Item = T> $($bound)* + 'a>(iter: I) -> Self {
+                Self(Box::new(iter))
+            }
+
+            /// Returns the boxed iterator
+            pub fn into_inner(self) -> Box<dyn Iterator<Item = T> $($bound)* + 'a> {
+                self.0
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl<'a, T> Iterator for $AnonIterDyn<'a, T> {
+            type Item = T;
+
+            fn next(&mut self) -> Option<T> {
+                self.0.next()
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.0.size_hint()
+            }
+
+            fn nth(&mut self, n: usize) -> Option<T> {
+                self.0.nth(n)
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl<T> fmt::Debug for $AnonIterDyn<'_, T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct(stringify!($AnonIterDyn)).finish_non_exhaustive()
+            }
+        }
+    };
+}
+
+create_dyn! {
+    /// A boxed `dyn Iterator`, for when the enums of this crate shouldn't be part of an API
+    ///
+    /// Every enum converts into it with [`From`], so code can switch between static
+    /// dispatch and type erasure without changing how the branches are written:
+    ///
+    /// ```rust
+    /// use anon_iter::{AnonIter2, AnonIterDyn};
+    ///
+    /// fn foo(x: i32) -> AnonIterDyn<'static, i32> {
+    ///     let it = if x == 0 {
+    ///         AnonIter2::I1(1..10)
+    ///     } else {
+    ///         AnonIter2::I2(core::iter::once(x))
+    ///     };
+    ///     it.into()
+    /// }
+    ///
+    /// assert_eq!(foo(5).collect::<Vec<_>>(), [5]);
+    /// ```
+    AnonIterDyn + Send
+}
+
+create_dyn! {
+    /// Like [`AnonIterDyn`], for iterators that aren't [`Send`]
+    AnonIterDynLocal
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: 'a> From<AnonIterDyn<'a, T>> for AnonIterDynLocal<'a, T> {
+    fn from(iter: AnonIterDyn<'a, T>) -> Self {
+        Self(iter.0)
+    }
+}
+
+/// A borrowed `dyn Iterator`, erasing the type of an iterator without an allocator
+///
+/// This is the dynamic dispatch escape hatch for `no_std` targets, for when the number of
+/// branches isn't known at compile time. `AnonIterDyn` owns its iterator instead, but needs
+/// the `alloc` feature. Every enum converts into it from a mutable reference:
+///
+/// ```rust
+/// use anon_iter::{AnonIter2, AnonIterRef};
+///
+/// let mut evens = (0..10).step_by(2);
+/// let mut odds = (1..10).step_by(2);
+/// let mut iters = [AnonIterRef::new(&mut evens), AnonIterRef::new(&mut odds)];
+/// assert_eq!(iters[1].next(), Some(1));
+///
+/// let mut anon: AnonIter2<_, core::iter::Empty<i32>> = AnonIter2::I1(1..4);
+/// assert_eq!(AnonIterRef::from(&mut anon).sum::<i32>(), 6);
+/// ```
+pub struct AnonIterRef<'a, T>(&'a mut dyn Iterator<Item = T>);
+
+impl<'a, T> AnonIterRef<'a, T> {
+    /// Borrows `iter`, erasing its type
+    pub fn new<I: Iterator<Item = T> + 'a>(iter: &'a mut I) -> Self {
+        Self(iter)
+    }
+
+    /// Returns the borrowed iterator
+    pub fn into_inner(self) -> &'a mut dyn Iterator<Item = T> {
+        self.0
+    }
+}
+
+impl<T> Iterator for AnonIterRef<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<T> {
+        self.0.nth(n)
+    }
+}
+
+impl<T> fmt::Debug for AnonIterRef<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AnonIterRef").finish_non_exhaustive()
+    }
+}
+
+/// An owned `dyn Iterator` stored inline in `N` bytes, erasing the type of an iterator without an allocator
+///
+/// Unlike `AnonIterDyn` it doesn't box the iterator, and unlike [`AnonIterRef`] it owns it.
+/// Any iterator of at most `N` bytes, aligned to at most 16 bytes, fits:
+///
+/// ```rust
+/// use anon_iter::AnonIterInline;
+///
+/// let mut iters: [AnonIterInline<'_, u32, 32>; 2] = [
+///     AnonIterInline::new(0..3),
+///     AnonIterInline::new(core::iter::once(4)),
+/// ];
+/// assert_eq!(iters[1].next(), Some(4));
+/// ```
+///
+/// An iterator that doesn't fit is a compile-time error:
+///
+/// ```rust,compile_fail
+/// use anon_iter::AnonIterInline;
+///
+/// // A `Range<u32>` takes 8 bytes
+/// let it: AnonIterInline<'_, u32, 4> = AnonIterInline::new(0..10);
+/// ```
+pub struct AnonIterInline<'a, T, const N: usize> {
+    buffer: InlineBuffer<N>,
+    vtable: &'a InlineVTable<T>,
+    /// The erased iterator may borrow for `'a`, and may be neither [`Send`] nor [`Sync`]
+    marker: PhantomData<(&'a (), *mut ())>,
+}
+
+/// The bytes of the iterator in an [`AnonIterInline`]
+#[repr(C, align(16))]
+struct InlineBuffer<const N: usize>([MaybeUninit<u8>; N]);
+
+/// The methods of the iterator in an [`AnonIterInline`]
+struct InlineVTable<T> {
+    next: unsafe fn(*mut u8) -> Option<T>,
+    size_hint: unsafe fn(*const u8) -> (usize, Option<usize>),
+    drop: unsafe fn(*mut u8),
+}
+
+/// The [`InlineVTable`] of `I`, and the check that `I` fits in `N` bytes
+struct Inline<I, const N: usize>(PhantomData<I>);
+
+impl<I: Iterator, const N: usize> Inline<I, N> {
+    #[cfg(anon_iter_const_panic)]
+    const FITS: () = assert!(
+        mem::size_of::<I>() <= N && mem::align_of::<I>() <= mem::align_of::<InlineBuffer<N>>(),
+        "the iterator doesn't fit in `AnonIterInline<'_, T, N>`, make `N` larger"
+    );
+
+    // Indexing out of bounds fails to compile as well, before `assert!` worked in constants
+    #[cfg(not(anon_iter_const_panic))]
+    const FITS: () = [()][!(mem::size_of::<I>() <= N
+        && mem::align_of::<I>() <= mem::align_of::<InlineBuffer<N>>())
+        as usize];
+
+    const VTABLE: InlineVTable<I::Item> = InlineVTable {
+        // SAFETY, for all 3: the buffer holds an `I`, which is dropped only once
+        next: |iter| unsafe { (*iter.cast::<I>()).next() },
+        size_hint: |iter| unsafe { (*iter.cast::<I>()).size_hint() },
+        drop: |iter| unsafe { iter.cast::<I>().drop_in_place() },
+    };
+}
+
+impl<'a, T, const N: usize> AnonIterInline<'a, T, N> {
+    /// Moves `iter` into the buffer, erasing its type
+    ///
+    /// Fails to compile unless `iter` fits in `N` bytes.
+    pub fn new<I: Iterator<Item = T> + 'a>(iter: I) -> Self {
+        let () = Inline::<I, N>::FITS;
+        let mut buffer = InlineBuffer([MaybeUninit::uninit(); N]);
+        // SAFETY: `FITS` checked that the buffer is large enough and aligned for an `I`
+        unsafe { buffer.0.as_mut_ptr().cast::<I>().write(iter) };
+        Self {
+            buffer,
+            vtable: &Inline::<I, N>::VTABLE,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T, const N: usize> Iterator for AnonIterInline<'_, T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        // SAFETY: `vtable` belongs to the iterator in `buffer`
+        unsafe { (self.vtable.next)(self.buffer.0.as_mut_ptr().cast()) }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // SAFETY: `vtable` belongs to the iterator in `buffer`
+        unsafe { (self.vtable.size_hint)(self.buffer.0.as_ptr().cast()) }
+    }
+}
+
+impl<T, const N: usize> Drop for AnonIterInline<'_, T, N> {
+    fn drop(&mut self) {
+        // SAFETY: `vtable` belongs to the iterator in `buffer`, which isn't used after this
+        unsafe { (self.vtable.drop)(self.buffer.0.as_mut_ptr().cast()) }
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for AnonIterInline<'_, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AnonIterInline").finish_non_exhaustive()
+    }
+}
+
+impl<I1, I2> AnonIter2<I1, I2> {
+    /// Swaps the variants: [`I1`](Self::I1) becomes [`I2`](Self::I2) and vice versa
+    pub fn flip(self) -> AnonIter2<I2, I1> {
+        match self {
+            Self::I1(i1) => AnonIter2::I2(i1),
+            Self::I2(i2) => AnonIter2::I1(i2),
+        }
+    }
+
+    /// Picks `i1` if `cond` is true, and `i2` otherwise
+    pub fn select(cond: bool, i1: I1, i2: I2) -> Self {
+        if cond {
+            Self::I1(i1)
+        } else {
+            Self::I2(i2)
+        }
+    }
+
+    /// [`Ok`] becomes [`I1`](Self::I1) and [`Err`] becomes [`I2`](Self::I2)
+    pub fn from_result(result: Result<I1, I2>) -> Self {
+        match result {
+            Ok(i1) => Self::I1(i1),
+            Err(i2) => Self::I2(i2),
+        }
+    }
+
+    /// [`Continue`](ControlFlow::Continue) becomes [`I1`](Self::I1) and [`Break`](ControlFlow::Break) becomes [`I2`](Self::I2)
+    #[cfg(anon_iter_control_flow)]
+    #[allow(clippy::incompatible_msrv)]
+    pub fn from_control_flow(control_flow: ControlFlow<I2, I1>) -> Self {
+        match control_flow {
+            ControlFlow::Continue(i1) => Self::I1(i1),
+            ControlFlow::Break(i2) => Self::I2(i2),
+        }
+    }
+}
+
+impl<I: Iterator> AnonIter2<I, Empty<I::Item>> {
+    /// Iterates over `iter`, for when the other branch iterates over nothing
+    pub fn some(iter: I) -> Self {
+        Self::I1(iter)
+    }
+
+    /// Iterates over nothing, for when the other branch iterates over an `I`
+    pub fn none() -> Self {
+        Self::I2(iter::empty())
+    }
+
+    /// Iterates over the iterator in `option`, if there is one
+    pub fn from_option(option: Option<I>) -> Self {
+        option.map_or_else(Self::none, Self::some)
+    }
+}
+
+/// [`Left`](either::Either::Left) becomes [`I1`](AnonIter2::I1) and [`Right`](either::Either::Right) becomes [`I2`](AnonIter2::I2)
+#[cfg(feature = "either")]
+impl<L, R> From<either::Either<L, R>> for AnonIter2<L, R> {
+    fn from(either: either::Either<L, R>) -> Self {
+        match either {
+            either::Either::Left(l) => Self::I1(l),
+            either::Either::Right(r) => Self::I2(r),
+        }
+    }
+}
+
+/// [`I1`](AnonIter2::I1) becomes [`Left`](either::Either::Left) and [`I2`](AnonIter2::I2) becomes [`Right`](either::Either::Right)
+#[cfg(feature = "either")]
+impl<L, R> From<AnonIter2<L, R>> for either::Either<L, R> {
+    fn from(anon: AnonIter2<L, R>) -> Self {
+        match anon {
+            AnonIter2::I1(l) => either::Either::Left(l),
+            AnonIter2::I2(r) => either::Either::Right(r),
+        }
+    }
+}
+
+#[cfg(feature = "either")]
+impl<I1, I2, I3> AnonIter3<I1, I2, I3> {
+    /// Flattens the usual way of nesting [`either::Either`] to return 3 iterators:
+    ///
+    /// - `Left(i1)` becomes [`I1`](Self::I1)
+    /// - `Right(Left(i2))` becomes [`I2`](Self::I2)
+    /// - `Right(Right(i3))` becomes [`I3`](Self::I3)
+    pub fn from_nested(nested: either::Either<I1, either::Either<I2, I3>>) -> Self {
+        use either::Either::{Left, Right};
+        match nested {
+            Left(i1) => Self::I1(i1),
+            Right(Left(i2)) => Self::I2(i2),
+            Right(Right(i3)) => Self::I3(i3),
+        }
+    }
+}
+
+#[cfg(all(feature = "either", feature = "arity-4"))]
+impl<I1, I2, I3, I4> AnonIter4<I1, I2, I3, I4> {
+    /// Flattens the usual way of nesting [`either::Either`] to return 4 iterators:
+    ///
+    /// - `Left(i1)` becomes [`I1`](Self::I1)
+    /// - `Right(Left(i2))` becomes [`I2`](Self::I2)
+    /// - `Right(Right(Left(i3)))` becomes [`I3`](Self::I3)
+    /// - `Right(Right(Right(i4)))` becomes [`I4`](Self::I4)
+    #[allow(clippy::type_complexity)]
+    pub fn from_nested(
+        nested: either::Either<I1, either::Either<I2, either::Either<I3, I4>>>,
+    ) -> Self {
+        use either::Either::{Left, Right};
+        match nested {
+            Left(i1) => Self::I1(i1),
+            Right(Left(i2)) => Self::I2(i2),
+            Right(Right(Left(i3))) => Self::I3(i3),
+            Right(Right(Right(i4))) => Self::I4(i4),
+        }
+    }
+}
+
+/// [`Left`](futures::future::Either::Left) becomes [`I1`](AnonIter2::I1) and [`Right`](futures::future::Either::Right) becomes [`I2`](AnonIter2::I2)
+#[cfg(feature = "futures")]
+impl<L, R> From<futures::future::Either<L, R>> for AnonIter2<L, R> {
+    fn from(either: futures::future::Either<L, R>) -> Self {
+        match either {
+            futures::future::Either::Left(l) => Self::I1(l),
+            futures::future::Either::Right(r) => Self::I2(r),
+        }
+    }
+}
+
+/// [`I1`](AnonIter2::I1) becomes [`Left`](futures::future::Either::Left) and [`I2`](AnonIter2::I2) becomes [`Right`](futures::future::Either::Right)
+#[cfg(feature = "futures")]
+impl<L, R> From<AnonIter2<L, R>> for futures::future::Either<L, R> {
+    fn from(anon: AnonIter2<L, R>) -> Self {
+        match anon {
+            AnonIter2::I1(l) => futures::future::Either::Left(l),
+            AnonIter2::I2(r) => futures::future::Either::Right(r),
+        }
+    }
+}
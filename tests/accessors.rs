@@ -0,0 +1,618 @@
+//! Checks the methods that inspect, extract or transform the active variant.
+
+use anon_iter::AnonIter3;
+use std::fmt;
+use std::iter::{once, Empty, Once, Skip, Take};
+use std::ops::Range;
+
+type Wrapped = AnonIter3<Empty<u32>, Range<u32>, Once<u32>>;
+
+#[test]
+fn is() {
+    let it: Wrapped = AnonIter3::I2(1..5);
+    assert!(!it.is_i1());
+    assert!(it.is_i2());
+    assert!(!it.is_i3());
+    assert!(Wrapped::I3(once(1)).is_i3());
+}
+
+#[test]
+fn as_ref() {
+    let it: Wrapped = AnonIter3::I2(1..5);
+    assert!(it.as_i1().is_none());
+    assert_eq!(it.as_i2(), Some(&(1..5)));
+    assert!(it.as_i3().is_none());
+}
+
+#[test]
+fn as_mut() {
+    let mut it: Wrapped = AnonIter3::I2(1..5);
+    assert!(it.as_i1_mut().is_none());
+    assert!(it.as_i3_mut().is_none());
+    let inner = it.as_i2_mut().unwrap();
+    assert_eq!(inner.by_ref().take(2).sum::<u32>(), 3);
+    inner.end = 6;
+    assert_eq!(it.collect::<Vec<_>>(), [3, 4, 5]);
+}
+
+#[test]
+fn into() {
+    let it: Wrapped = AnonIter3::I2(1..5);
+    let it = it.into_i1().unwrap_err();
+    let it = it.into_i3().unwrap_err();
+    assert_eq!(it.into_i2().unwrap(), 1..5);
+}
+
+#[test]
+fn unwrap() {
+    assert_eq!(Wrapped::I2(1..5).unwrap_i2(), 1..5);
+    assert_eq!(Wrapped::I2(1..5).expect_i2("a range"), 1..5);
+}
+
+#[test]
+#[should_panic(expected = "called `Anon3::unwrap_i3()` on an `I2` value")]
+fn unwrap_other() {
+    let _ = Wrapped::I2(1..5).unwrap_i3();
+}
+
+#[test]
+#[should_panic(expected = "wanted the empty iterator")]
+fn expect_other() {
+    let _ = Wrapped::I2(1..5).expect_i1("wanted the empty iterator");
+}
+
+#[test]
+fn variant_index() {
+    assert_eq!(Wrapped::I1(std::iter::empty()).variant_index(), 1);
+    assert_eq!(Wrapped::I2(1..5).variant_index(), 2);
+    assert_eq!(Wrapped::I3(once(1)).variant_index(), 3);
+}
+
+#[test]
+fn variant_name() {
+    assert_eq!(Wrapped::I1(std::iter::empty()).variant_name(), "I1");
+    assert_eq!(Wrapped::I2(1..5).variant_name(), "I2");
+    assert_eq!(Wrapped::I3(once(1)).variant_name(), "I3");
+}
+
+#[test]
+fn map() {
+    let it: Wrapped = AnonIter3::I2(1..5);
+    let it = it.map_i2(|range| range.map(|x| x * 10));
+    assert_eq!(it.collect::<Vec<_>>(), [10, 20, 30, 40]);
+
+    let it: Wrapped = AnonIter3::I3(once(7));
+    type Mapped = AnonIter3<Empty<u32>, Skip<Range<u32>>, Take<Once<u32>>>;
+    let it: Mapped = it.map_i2(|range| range.skip(1)).map_i3(|once| once.take(1));
+    assert_eq!(it.unwrap_i3().collect::<Vec<_>>(), [7]);
+}
+
+#[test]
+fn map_all() {
+    let it: Wrapped = AnonIter3::I2(1..5);
+    let it = it.map_all(
+        |empty| empty.map(|x| x.to_string()),
+        |range| range.map(|x| x.to_string()),
+        |once| once.map(|x| x.to_string()),
+    );
+    assert!(it.is_i2());
+    assert_eq!(it.collect::<String>(), "1234");
+}
+
+#[test]
+fn fold_variants() {
+    let describe = |it: Wrapped| {
+        it.fold_variants(
+            |_| "nothing".to_string(),
+            |range| format!("{} numbers", range.len()),
+            |once| format!("just {}", once.sum::<u32>()),
+        )
+    };
+    assert_eq!(describe(AnonIter3::I1(std::iter::empty())), "nothing");
+    assert_eq!(describe(AnonIter3::I2(1..5)), "4 numbers");
+    assert_eq!(describe(AnonIter3::I3(once(7))), "just 7");
+}
+
+#[test]
+fn as_mut_projection() {
+    let mut it: Wrapped = AnonIter3::I2(1..5);
+    assert_eq!(it.as_mut().take(2).collect::<Vec<_>>(), [1, 2]);
+    assert_eq!(it.as_i2(), Some(&(3..5)));
+    assert_eq!(it.as_mut().variant_index(), 2);
+}
+
+#[test]
+fn into_inner() {
+    let it: AnonIter3<Range<u32>, Range<u32>, Range<u32>> = AnonIter3::I3(1..5);
+    assert_eq!(it.into_inner(), 1..5);
+}
+
+#[test]
+fn into_common() {
+    /// Every variant converts into this
+    struct Common(&'static str);
+
+    impl From<Empty<u32>> for Common {
+        fn from(_: Empty<u32>) -> Self {
+            Common("empty")
+        }
+    }
+
+    impl From<Range<u32>> for Common {
+        fn from(_: Range<u32>) -> Self {
+            Common("range")
+        }
+    }
+
+    impl From<Once<u32>> for Common {
+        fn from(_: Once<u32>) -> Self {
+            Common("once")
+        }
+    }
+
+    assert_eq!(Wrapped::I2(1..5).into_common::<Common>().0, "range");
+    assert_eq!(Wrapped::I3(once(1)).into_common::<Common>().0, "once");
+}
+
+#[test]
+fn flip() {
+    use anon_iter::AnonIter2;
+
+    let it: AnonIter2<Range<u32>, Once<u32>> = AnonIter2::I1(1..5);
+    let it: AnonIter2<Once<u32>, Range<u32>> = it.flip();
+    assert_eq!(it.as_i2(), Some(&(1..5)));
+    assert_eq!(it.flip().unwrap_i1(), 1..5);
+}
+
+#[test]
+fn rotate() {
+    let it: Wrapped = AnonIter3::I2(1..5);
+    let left: AnonIter3<Range<u32>, Once<u32>, Empty<u32>> = it.clone().rotate_left();
+    assert_eq!(left.as_i1(), Some(&(1..5)));
+    let right: AnonIter3<Once<u32>, Empty<u32>, Range<u32>> = it.rotate_right();
+    assert_eq!(right.as_i3(), Some(&(1..5)));
+    assert!(right.rotate_left().is_i2());
+
+    let it: Wrapped = AnonIter3::I1(std::iter::empty());
+    assert!(it.clone().rotate_left().is_i3());
+    assert!(it.rotate_right().is_i2());
+}
+
+#[test]
+fn iterator_ext() {
+    use anon_iter::IteratorExt as _;
+
+    let it: Wrapped = (1..5).anon_i2_of_3();
+    assert_eq!(it.as_i2(), Some(&(1..5)));
+    let it: Wrapped = once(1).anon_i3_of_3();
+    assert!(it.is_i3());
+
+    #[cfg(feature = "arity-12")]
+    {
+        use anon_iter::AnonIter12;
+
+        type Wide = AnonIter12<
+            Empty<u32>,
+            Empty<u32>,
+            Empty<u32>,
+            Empty<u32>,
+            Empty<u32>,
+            Empty<u32>,
+            Empty<u32>,
+            Empty<u32>,
+            Empty<u32>,
+            Empty<u32>,
+            Empty<u32>,
+            Range<u32>,
+        >;
+        let it: Wide = (1..5).anon_i12_of_12();
+        assert_eq!(it.variant_index(), 12);
+    }
+}
+
+#[test]
+fn free_functions() {
+    use anon_iter::{of1, of3};
+
+    let it: Wrapped = of3::i2(1..5);
+    assert_eq!(it.as_i2(), Some(&(1..5)));
+    let it: Wrapped = of3::i1(std::iter::empty());
+    assert!(it.is_i1());
+    let it: anon_iter::AnonIter1<_> = of1::i1(1..5);
+    assert_eq!(it.as_i1(), Some(&(1..5)));
+
+    #[cfg(feature = "arity-12")]
+    {
+        use anon_iter::{of12, AnonIter12};
+
+        type Wide = AnonIter12<
+            Range<u32>,
+            Empty<u32>,
+            Empty<u32>,
+            Empty<u32>,
+            Empty<u32>,
+            Empty<u32>,
+            Empty<u32>,
+            Empty<u32>,
+            Empty<u32>,
+            Empty<u32>,
+            Empty<u32>,
+            Empty<u32>,
+        >;
+        let it: Wide = of12::i1(1..5);
+        assert_eq!(it.collect::<Vec<_>>(), [1, 2, 3, 4]);
+    }
+}
+
+#[test]
+fn prelude() {
+    use anon_iter::prelude::*;
+
+    let it: A3<Empty<u32>, Range<u32>, Once<u32>> = A3::I2(1..5);
+    let it: Wrapped = it;
+    assert_eq!(it.as_i2(), Some(&(1..5)));
+    let it: A2<Range<u32>, Once<u32>> = once(1).anon_i2_of_2();
+    assert!(it.is_i2());
+}
+
+#[test]
+fn arity_modules() {
+    let it: anon_iter::arity3::AnonIter3<_, _, _> = Wrapped::I2(1..5);
+    let it: Wrapped = it;
+    assert!(it.is_i2());
+}
+
+#[test]
+fn as_pin_mut() {
+    let mut it: Wrapped = AnonIter3::I2(1..5);
+    let inner = std::pin::Pin::new(&mut it).as_pin_mut().unwrap_i2();
+    assert_eq!(inner.get_mut().next(), Some(1));
+    assert_eq!(it.collect::<Vec<_>>(), [2, 3, 4]);
+}
+
+#[test]
+fn items_into() {
+    use anon_iter::{AnonIter2, AnonIterInto2};
+
+    type Words = AnonIterInto2<String, std::vec::IntoIter<&'static str>, Once<String>>;
+    let it: Words = AnonIter2::I1(vec!["a", "b"].into_iter()).items_into();
+    assert_eq!(it.size_hint(), (2, Some(2)));
+    #[cfg(feature = "double-ended")]
+    assert_eq!(it.clone().rev().collect::<Vec<_>>(), ["b", "a"]);
+    assert_eq!(it.collect::<Vec<_>>(), ["a", "b"]);
+
+    let it: Words = AnonIter2::I2(once(String::from("c"))).items_into();
+    assert_eq!(it.into_inner().unwrap_i2().collect::<Vec<_>>(), ["c"]);
+
+    let numbers: AnonIterInto2<u64, Range<u8>, Once<u32>> = AnonIter2::I1(1..4).items_into();
+    assert_eq!(numbers.sum::<u64>(), 6);
+}
+
+#[test]
+fn anon_items() {
+    use anon_iter::{AnonItem2, AnonIter2, AnonIterHetero2};
+
+    type Parsed = AnonIterHetero2<Range<u32>, std::str::Chars<'static>>;
+    let it: Parsed = AnonIter2::I2("ab".chars()).anon_items();
+    #[cfg(feature = "double-ended")]
+    assert_eq!(it.clone().next_back(), Some(AnonItem2::I2('b')));
+    assert_eq!(
+        it.collect::<Vec<_>>(),
+        [AnonItem2::I2('a'), AnonItem2::I2('b')]
+    );
+
+    let it: Parsed = AnonIter2::I1(1..3).anon_items();
+    assert_eq!(it.size_hint(), (2, Some(2)));
+    assert!(it.into_inner().is_i1());
+}
+
+#[test]
+fn anon_errors() {
+    use anon_iter::{AnonErr2, AnonIter2, AnonTryIter2};
+    use std::num::ParseIntError;
+
+    type Parsed =
+        AnonTryIter2<std::vec::IntoIter<Result<u8, ParseIntError>>, Once<Result<u8, fmt::Error>>>;
+    let it: Parsed = AnonIter2::I1(vec!["1".parse(), "x".parse()].into_iter()).anon_errors();
+    #[cfg(feature = "exact-size")]
+    assert_eq!(it.len(), 2);
+    let items = it.collect::<Vec<_>>();
+    assert_eq!(items[0], Ok(1));
+    assert!(matches!(items[1], Err(AnonErr2::I1(_))));
+
+    let it: Parsed = AnonIter2::I2(once(Err(fmt::Error))).anon_errors();
+    let error = it.collect::<Result<Vec<_>, _>>().unwrap_err();
+    assert_eq!(error, AnonErr2::I2(fmt::Error));
+    #[cfg(feature = "fmt")]
+    let _: &dyn std::error::Error = &error;
+}
+
+#[test]
+fn lite() {
+    use anon_iter::{AnonIter3, AnonIterLite};
+
+    type Lite = AnonIterLite<AnonIter3<Range<u32>, Once<u32>, std::vec::IntoIter<u32>>>;
+    let it: Lite = AnonIter3::I3(vec![1, 2, 3, 4].into_iter()).lite();
+    assert_eq!(it.size_hint(), (4, Some(4)));
+    assert_eq!(it.clone().count(), 4);
+    #[cfg(feature = "double-ended")]
+    assert_eq!(it.clone().rev().collect::<Vec<_>>(), [4, 3, 2, 1]);
+    let mut it = it;
+    assert_eq!(it.nth(1), Some(2));
+    #[cfg(feature = "exact-size")]
+    assert_eq!(it.len(), 2);
+
+    let mut it: Lite = AnonIter3::I1(0..10).lite();
+    assert_eq!(it.nth(8), Some(8));
+    assert_eq!(it.max(), Some(9));
+    let it: Lite = AnonIter3::I2(once(5)).lite();
+    assert!(it.into_inner().is_i2());
+}
+
+#[test]
+fn into_iters() {
+    use anon_iter::AnonIter2;
+
+    fn evens(cached: bool) -> impl Iterator<Item = u32> {
+        if cached {
+            AnonIter2::I1(vec![0, 2, 4])
+        } else {
+            AnonIter2::I2((0..6).step_by(2))
+        }
+        .into_iters()
+    }
+
+    assert_eq!(evens(true).collect::<Vec<_>>(), [0, 2, 4]);
+    assert_eq!(evens(false).collect::<Vec<_>>(), [0, 2, 4]);
+}
+
+#[test]
+fn non_iterators() {
+    // Nothing but the `Iterator` impls asks for the variants to be iterators
+    struct Holds<A, B> {
+        value: anon_iter::AnonIter2<A, B>,
+    }
+
+    let holds = Holds {
+        value: anon_iter::AnonIter2::<u8, char>::I2('x'),
+    };
+    assert_eq!(holds.value.variant_index(), 2);
+    assert_eq!(holds.value.clone(), holds.value);
+    assert_eq!(holds.value.flip().unwrap_i1(), 'x');
+}
+
+#[cfg(feature = "high-arity")]
+#[test]
+fn high_arity() {
+    type Wide = anon_iter::AnonIter16<
+        Empty<u32>,
+        Range<u32>,
+        Once<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        Once<u32>,
+    >;
+
+    let it: Wide = anon_iter::of16::i16(once(7));
+    assert_eq!(it.variant_index(), 16);
+    assert_eq!(it.unwrap_i16().sum::<u32>(), 7);
+    let it: Wide = Wrapped::I2(1..5).into();
+    assert_eq!(it.variant_name(), "I2");
+    assert_eq!(it.rev().collect::<Vec<_>>(), [4, 3, 2, 1]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn boxed() {
+    let it: Box<dyn Iterator<Item = u32> + Send> = Wrapped::I2(1..5).boxed();
+    assert_eq!(it.collect::<Vec<_>>(), [1, 2, 3, 4]);
+
+    let rc = std::rc::Rc::new(7);
+    let local: AnonIter3<Empty<u32>, Range<u32>, std::iter::Map<Once<()>, _>> =
+        AnonIter3::I3(once(()).map(move |()| *rc));
+    let it: Box<dyn Iterator<Item = u32>> = local.boxed_local();
+    assert_eq!(it.collect::<Vec<_>>(), [7]);
+}
+
+#[test]
+fn anon_iter_ref() {
+    use anon_iter::AnonIterRef;
+
+    let mut wrapped = Wrapped::I2(1..5);
+    let mut it = AnonIterRef::from(&mut wrapped);
+    assert_eq!(it.size_hint(), (4, Some(4)));
+    assert_eq!(it.nth(1), Some(2));
+    assert_eq!(format!("{:?}", it), "AnonIterRef { .. }");
+    assert_eq!(it.into_inner().next(), Some(3));
+    assert_eq!(wrapped.collect::<Vec<_>>(), [4]);
+}
+
+#[test]
+fn anon_iter_inline() {
+    use anon_iter::AnonIterInline;
+    use std::rc::Rc;
+
+    let it: AnonIterInline<'_, u32, 16> = Wrapped::I2(1..5).into();
+    assert_eq!(it.size_hint(), (4, Some(4)));
+    assert_eq!(it.collect::<Vec<_>>(), [1, 2, 3, 4]);
+
+    let items = [7, 8];
+    let mut it: AnonIterInline<'_, u32, 16> = AnonIterInline::new(items.iter().copied());
+    assert_eq!(it.next(), Some(7));
+    assert_eq!(format!("{:?}", it), "AnonIterInline { .. }");
+
+    // The iterator is dropped with the wrapper, exactly once
+    let rc = Rc::new(());
+    let it: AnonIterInline<'_, Rc<()>, 8> = AnonIterInline::new(std::iter::repeat(Rc::clone(&rc)));
+    assert_eq!(it.take(2).count(), 2);
+    assert_eq!(Rc::strong_count(&rc), 1);
+
+    let mut it: AnonIterInline<'_, (), 0> = AnonIterInline::new(std::iter::empty());
+    assert_eq!(it.next(), None);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn anon_iter_dyn() {
+    use anon_iter::{AnonIter2, AnonIterDyn, AnonIterDynLocal};
+
+    let it: AnonIterDyn<'_, u32> = Wrapped::I2(1..5).into();
+    assert_eq!(it.size_hint(), (4, Some(4)));
+    let mut it: AnonIterDynLocal<'_, u32> = it.into();
+    assert_eq!(it.nth(1), Some(2));
+    assert_eq!(it.collect::<Vec<_>>(), [3, 4]);
+
+    let rc = std::rc::Rc::new(7);
+    let local: AnonIter2<Empty<u32>, std::iter::Map<Once<()>, _>> =
+        AnonIter2::I2(once(()).map(move |()| *rc));
+    let it = AnonIterDynLocal::from(local);
+    assert_eq!(format!("{:?}", it), "AnonIterDynLocal { .. }");
+    assert_eq!(it.into_inner().collect::<Vec<_>>(), [7]);
+}
+
+#[test]
+fn debug_variant() {
+    struct NotDebug(Range<u32>);
+    impl Iterator for NotDebug {
+        type Item = u32;
+        fn next(&mut self) -> Option<u32> {
+            self.0.next()
+        }
+    }
+
+    let it: AnonIter3<Empty<u32>, NotDebug, Once<u32>> = AnonIter3::I2(NotDebug(1..5));
+    let variant = it.debug_variant();
+    assert_eq!(variant.to_string(), "I2");
+    assert_eq!(
+        format!("{:?}", variant),
+        r#"DebugVariant { name: "I2", index: 2 }"#
+    );
+    assert_eq!((variant.name(), variant.index()), ("I2", 2));
+}
+
+#[test]
+fn inspect_variant() {
+    use std::cell::Cell;
+
+    let report = Cell::new(None);
+    let it = Wrapped::I2(1..5).inspect_variant(|variant, count| {
+        report.set(Some((variant.index(), count)));
+    });
+    assert_eq!(it.sum::<u32>(), 10);
+    assert_eq!(report.get(), Some((2, 4)));
+
+    // Dropping the adapter before the end reports the items yielded so far
+    let mut it = Wrapped::I2(1..5).inspect_variant(|variant, count| {
+        report.set(Some((variant.index(), count)));
+    });
+    it.next();
+    assert_eq!(report.get(), Some((2, 4)));
+    drop(it);
+    assert_eq!(report.get(), Some((2, 1)));
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn instrument() {
+    use std::fmt::Debug;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    /// Records the fields of every event as `name=value`
+    #[derive(Default)]
+    struct Fields(Arc<Mutex<Vec<String>>>);
+
+    impl Visit for Fields {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            let field = format!("{}={:?}", field.name(), value);
+            self.0.lock().unwrap().push(field);
+        }
+    }
+
+    impl Subscriber for Fields {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, _: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+        fn event(&self, event: &Event<'_>) {
+            event.record(&mut Fields(Arc::clone(&self.0)));
+        }
+        fn enter(&self, _: &Id) {}
+        fn exit(&self, _: &Id) {}
+    }
+
+    let fields = Fields::default();
+    let recorded = Arc::clone(&fields.0);
+    tracing::subscriber::with_default(fields, || {
+        assert_eq!(Wrapped::I2(1..5).instrument("numbers").count(), 4);
+    });
+    assert_eq!(
+        *recorded.lock().unwrap(),
+        [
+            "message=iteration ended",
+            "iter=\"numbers\"",
+            "variant=\"I2\"",
+            "index=2",
+            "items=4"
+        ]
+    );
+}
+
+#[test]
+fn from_result() {
+    use anon_iter::AnonIter2;
+    use std::ops::ControlFlow;
+
+    type Two = AnonIter2<Range<u32>, Once<u32>>;
+    assert_eq!(Two::from_result(Ok(1..5)).as_i1(), Some(&(1..5)));
+    assert!(Two::from_result(Err(once(7))).is_i2());
+    assert_eq!(
+        Two::from_control_flow(ControlFlow::Continue(1..5)).as_i1(),
+        Some(&(1..5))
+    );
+    assert!(Two::from_control_flow(ControlFlow::Break(once(7))).is_i2());
+}
+
+#[test]
+fn option() {
+    use anon_iter::AnonIter2;
+
+    type Maybe = AnonIter2<Range<u32>, Empty<u32>>;
+    assert_eq!(Maybe::some(1..5).collect::<Vec<_>>(), [1, 2, 3, 4]);
+    assert_eq!(Maybe::none().next(), None);
+    assert!(Maybe::from_option(Some(1..5)).is_i1());
+    assert!(Maybe::from_option(None).is_i2());
+}
+
+#[test]
+fn select() {
+    use anon_iter::AnonIter2;
+
+    type Two = AnonIter2<Range<u32>, Once<u32>>;
+    assert!(Two::select(true, 1..5, once(7)).is_i1());
+    assert!(Two::select(false, 1..5, once(7)).is_i2());
+
+    let it = Wrapped::select(2, (std::iter::empty(), 1..5, once(7))).unwrap();
+    assert_eq!(it.as_i2(), Some(&(1..5)));
+    assert!(Wrapped::select(3, (std::iter::empty(), 1..5, once(7)))
+        .unwrap()
+        .is_i3());
+    assert!(Wrapped::select(0, (std::iter::empty(), 1..5, once(7))).is_none());
+    assert!(Wrapped::select(4, (std::iter::empty(), 1..5, once(7))).is_none());
+}
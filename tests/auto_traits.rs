@@ -0,0 +1,97 @@
+//! Checks that the enum has the auto traits when all variants do, and that a
+//! variant missing an auto trait also takes it away from the enum.
+
+use anon_iter::{AnonIter1, AnonIter3};
+use static_assertions::{assert_impl_all, assert_not_impl_any};
+use std::cell::Cell;
+use std::iter::Empty;
+use std::marker::PhantomPinned;
+use std::ops::Range;
+use std::panic::{RefUnwindSafe, UnwindSafe};
+use std::rc::Rc;
+
+/// Neither `Sync` nor `RefUnwindSafe`, because of the `Cell`
+struct Counter(Cell<u32>);
+
+impl Iterator for Counter {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        self.0.set(self.0.get() + 1);
+        Some(self.0.get())
+    }
+}
+
+/// Not `Unpin`
+#[allow(dead_code)]
+struct Pinned(PhantomPinned);
+
+impl Iterator for Pinned {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        None
+    }
+}
+
+assert_impl_all!(AnonIter1<Range<u32>>: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_impl_all!(
+    AnonIter3<Range<u32>, Empty<u32>, std::vec::IntoIter<u32>>: Send,
+    Sync,
+    Unpin,
+    UnwindSafe,
+    RefUnwindSafe
+);
+#[cfg(feature = "arity-12")]
+assert_impl_all!(
+    anon_iter::AnonIter12<
+        Range<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        std::vec::IntoIter<u32>,
+    >: Send,
+    Sync,
+    Unpin,
+    UnwindSafe,
+    RefUnwindSafe
+);
+#[cfg(feature = "high-arity")]
+assert_impl_all!(
+    anon_iter::AnonIter16<
+        Range<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        Empty<u32>,
+        std::vec::IntoIter<u32>,
+    >: Send,
+    Sync,
+    Unpin,
+    UnwindSafe,
+    RefUnwindSafe
+);
+
+assert_not_impl_any!(
+    AnonIter3<Empty<Rc<u32>>, Empty<Rc<u32>>, std::vec::IntoIter<Rc<u32>>>: Send
+);
+assert_not_impl_any!(AnonIter3<Range<u32>, Range<u32>, Counter>: Sync, RefUnwindSafe);
+assert_not_impl_any!(AnonIter3<Range<u32>, Range<u32>, Pinned>: Unpin);
+assert_not_impl_any!(AnonIter3<Range<u32>, Range<u32>, &'static mut Range<u32>>: UnwindSafe);
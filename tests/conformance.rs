@@ -0,0 +1,186 @@
+//! Checks that for random inputs, every forwarded method returns exactly what
+//! the same method returns on the wrapped iterator.
+#![cfg_attr(
+    all(feature = "nightly", feature = "exact-size"),
+    feature(exact_size_is_empty)
+)]
+#![cfg_attr(
+    feature = "nightly",
+    feature(iter_advance_by, iter_is_partitioned, iter_next_chunk, iter_order_by)
+)]
+
+use anon_iter::AnonIter3;
+use proptest::prelude::*;
+use std::vec::IntoIter;
+
+type Wrapped<T> = AnonIter3<IntoIter<T>, IntoIter<T>, IntoIter<T>>;
+
+/// Wraps `items` in the variant at `variant`, so every match arm gets tested
+fn wrap<T>(items: Vec<T>, variant: usize) -> Wrapped<T> {
+    match variant {
+        0 => AnonIter3::I1(items.into_iter()),
+        1 => AnonIter3::I2(items.into_iter()),
+        _ => AnonIter3::I3(items.into_iter()),
+    }
+}
+
+/// Calls `$call` on the wrapped and the plain iterator, and compares the results
+macro_rules! assert_conforms {
+    ($items:expr, $variant:expr, |$it:ident| $call:expr) => {{
+        let expected = {
+            #[allow(unused_mut)]
+            let mut $it = $items.clone().into_iter();
+            $call
+        };
+        let actual = {
+            #[allow(unused_mut)]
+            let mut $it = wrap($items.clone(), $variant);
+            $call
+        };
+        prop_assert_eq!(actual, expected, "{}", stringify!($call));
+    }};
+}
+
+/// A single method call that borrows the iterator, so several can be chained
+#[cfg(all(feature = "double-ended", feature = "exact-size"))]
+#[derive(Debug, Clone)]
+enum Step {
+    Next,
+    NextBack,
+    Nth(usize),
+    NthBack(usize),
+    SizeHint,
+    Len,
+}
+
+#[cfg(all(feature = "double-ended", feature = "exact-size"))]
+#[derive(Debug, PartialEq)]
+enum Output {
+    Item(Option<u8>),
+    SizeHint((usize, Option<usize>)),
+    Len(usize),
+}
+
+#[cfg(all(feature = "double-ended", feature = "exact-size"))]
+impl Step {
+    fn call<I: DoubleEndedIterator<Item = u8> + ExactSizeIterator>(&self, it: &mut I) -> Output {
+        match *self {
+            Step::Next => Output::Item(it.next()),
+            Step::NextBack => Output::Item(it.next_back()),
+            Step::Nth(n) => Output::Item(it.nth(n)),
+            Step::NthBack(n) => Output::Item(it.nth_back(n)),
+            Step::SizeHint => Output::SizeHint(it.size_hint()),
+            Step::Len => Output::Len(it.len()),
+        }
+    }
+}
+
+#[cfg(all(feature = "double-ended", feature = "exact-size"))]
+fn step() -> impl Strategy<Value = Step> {
+    prop_oneof![
+        Just(Step::Next),
+        Just(Step::NextBack),
+        (0..8usize).prop_map(Step::Nth),
+        (0..8usize).prop_map(Step::NthBack),
+        Just(Step::SizeHint),
+        Just(Step::Len),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn iterator(items: Vec<u8>, variant in 0..3usize, n in 0..40usize, x: u8) {
+        assert_conforms!(items, variant, |it| it.next());
+        assert_conforms!(items, variant, |it| it.size_hint());
+        assert_conforms!(items, variant, |it| it.fold(0u32, |acc, y| acc.wrapping_mul(31) ^ u32::from(y)));
+        assert_conforms!(items, variant, |it| it.nth(n));
+        assert_conforms!(items, variant, |it| it.count());
+        assert_conforms!(items, variant, |it| it.last());
+        assert_conforms!(items, variant, |it| {
+            let mut seen = Vec::new();
+            it.for_each(|x| seen.push(x));
+            seen
+        });
+        assert_conforms!(items, variant, |it| it.find(|&y| y > x));
+        assert_conforms!(items, variant, |it| it.position(|y| y == x));
+        assert_conforms!(items, variant, |it| it.any(|y| y == x));
+        assert_conforms!(items, variant, |it| it.all(|y| y < x));
+        assert_conforms!(items, variant, |it| it.max());
+        assert_conforms!(items, variant, |it| it.min());
+        assert_conforms!(items, variant, |it| it.max_by_key(|&y| y % 7));
+        assert_conforms!(items, variant, |it| it.max_by(|a, b| (a % 7).cmp(&(b % 7))));
+        assert_conforms!(items, variant, |it| it.min_by_key(|&y| y % 7));
+        assert_conforms!(items, variant, |it| it.min_by(|a, b| (a % 7).cmp(&(b % 7))));
+        assert_conforms!(items, variant, |it| it.reduce(|a, b| a.wrapping_mul(3) ^ b));
+        assert_conforms!(items, variant, |it| it.is_sorted());
+        assert_conforms!(items, variant, |it| it.is_sorted_by(|a, b| a >= b));
+        assert_conforms!(items, variant, |it| it.is_sorted_by_key(|y| y / 16));
+        assert_conforms!(items, variant, |it| it.cmp(items.iter().copied().rev()));
+        assert_conforms!(items, variant, |it| it.partial_cmp(items.iter().copied().skip(1)));
+        assert_conforms!(items, variant, |it| it.eq(items.iter().copied()));
+        assert_conforms!(items, variant, |it| it.ne(items.iter().copied().take(n)));
+        assert_conforms!(items, variant, |it| it.lt(items.iter().copied().rev()));
+        assert_conforms!(items, variant, |it| it.le(items.iter().copied().rev()));
+        assert_conforms!(items, variant, |it| it.gt(items.iter().copied().rev()));
+        assert_conforms!(items, variant, |it| it.ge(items.iter().copied().rev()));
+        assert_conforms!(items, variant, |it| it.partition::<Vec<_>, _>(|&y| y < x));
+        assert_conforms!(items, variant, |it| it.collect::<Vec<_>>());
+        assert_conforms!(items, variant, |it| it.find_map(|y| y.checked_sub(x)));
+    }
+
+    /// Small enough that neither the sum nor the product overflows
+    #[test]
+    fn sum_product(items in prop::collection::vec(0..16u64, 0..16), variant in 0..3usize) {
+        assert_conforms!(items, variant, |it| it.sum::<u64>());
+        assert_conforms!(items, variant, |it| it.product::<u64>());
+    }
+
+    #[cfg(feature = "double-ended")]
+    #[test]
+    fn double_ended_iterator(items: Vec<u8>, variant in 0..3usize, n in 0..40usize, x: u8) {
+        assert_conforms!(items, variant, |it| it.next_back());
+        assert_conforms!(items, variant, |it| it.rfold(0u32, |acc, y| acc.wrapping_mul(31) ^ u32::from(y)));
+        assert_conforms!(items, variant, |it| it.nth_back(n));
+        assert_conforms!(items, variant, |it| it.rfind(|&y| y > x));
+    }
+
+    #[cfg(feature = "exact-size")]
+    #[test]
+    fn exact_size_iterator(items: Vec<u8>, variant in 0..3usize) {
+        assert_conforms!(items, variant, |it| it.len());
+    }
+
+    /// Partially consumed iterators must keep agreeing, from both ends
+    #[cfg(all(feature = "double-ended", feature = "exact-size"))]
+    #[test]
+    fn interleaved(items: Vec<u8>, variant in 0..3usize, steps in prop::collection::vec(step(), 0..32)) {
+        let mut expected = items.clone().into_iter();
+        let mut actual = wrap(items, variant);
+        for step in &steps {
+            prop_assert_eq!(step.call(&mut actual), step.call(&mut expected), "{:?}", step);
+        }
+        prop_assert_eq!(actual.collect::<Vec<_>>(), expected.collect::<Vec<_>>());
+    }
+
+    #[cfg(feature = "nightly")]
+    #[test]
+    fn nightly(items: Vec<u8>, variant in 0..3usize, n in 0..40usize, x: u8) {
+        assert_conforms!(items, variant, |it| (it.try_fold(0u8, |acc, y| acc.checked_add(y)), it.next()));
+        #[cfg(feature = "double-ended")]
+        assert_conforms!(items, variant, |it| (it.try_rfold(0u8, |acc, y| acc.checked_add(y)), it.next_back()));
+        assert_conforms!(items, variant, |it| (it.try_for_each(|y| if y < x { Ok(()) } else { Err(y) }), it.next()));
+        assert_conforms!(items, variant, |it| (it.advance_by(n), it.next()));
+        #[cfg(feature = "double-ended")]
+        assert_conforms!(items, variant, |it| (it.advance_back_by(n), it.next_back()));
+        #[cfg(feature = "exact-size")]
+        assert_conforms!(items, variant, |it| it.is_empty());
+        assert_conforms!(items, variant, |it| (
+            it.next_chunk::<4>().map_err(|rest| rest.collect::<Vec<_>>()),
+            it.next()
+        ));
+        assert_conforms!(items, variant, |it| it.is_partitioned(|y| y < x));
+        assert_conforms!(items, variant, |it| it.cmp_by(items.iter().rev(), |a, &b| a.cmp(&b)));
+        assert_conforms!(items, variant, |it| it.partial_cmp_by(items.iter().rev(), |a, &b| a.partial_cmp(&b)));
+        assert_conforms!(items, variant, |it| it.eq_by(items.iter().rev(), |a, &b| a == b));
+    }
+}
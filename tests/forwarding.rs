@@ -0,0 +1,514 @@
+//! Checks that every forwarded method reaches the wrapped iterator exactly once,
+//! instead of going through the default implementation built on `next`.
+#![cfg_attr(
+    feature = "nightly",
+    feature(
+        exact_size_is_empty,
+        iter_advance_by,
+        iter_collect_into,
+        iter_is_partitioned,
+        iter_next_chunk,
+        iter_order_by,
+        try_trait_v2
+    )
+)]
+// The tests need a compiler new enough for every forwarded method, not the MSRV
+#![allow(clippy::incompatible_msrv)]
+
+use anon_iter::AnonIter3;
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::iter::{Empty, FromIterator, Product, Sum};
+#[cfg(feature = "nightly")]
+use std::num::NonZero;
+use std::ops::Range;
+#[cfg(feature = "nightly")]
+use std::ops::Try;
+
+/// An iterator that records the name of every `Iterator` method called on it
+struct Probe<'a, I> {
+    inner: I,
+    log: &'a RefCell<Vec<&'static str>>,
+}
+
+impl<I> Probe<'_, I> {
+    fn log(&self, method: &'static str) {
+        self.log.borrow_mut().push(method);
+    }
+}
+
+impl<I: Iterator> Iterator for Probe<'_, I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.log("next");
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.log("size_hint");
+        self.inner.size_hint()
+    }
+
+    fn fold<B, F: FnMut(B, Self::Item) -> B>(self, init: B, f: F) -> B {
+        self.log("fold");
+        self.inner.fold(init, f)
+    }
+
+    #[cfg(feature = "nightly")]
+    fn try_fold<B, F, R>(&mut self, init: B, f: F) -> R
+    where
+        F: FnMut(B, Self::Item) -> R,
+        R: Try<Output = B>,
+    {
+        self.log("try_fold");
+        self.inner.try_fold(init, f)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.log("nth");
+        self.inner.nth(n)
+    }
+
+    #[cfg(feature = "nightly")]
+    fn advance_by(&mut self, n: usize) -> Result<(), NonZero<usize>> {
+        self.log("advance_by");
+        self.inner.advance_by(n)
+    }
+
+    #[cfg(feature = "nightly")]
+    fn next_chunk<const N: usize>(
+        &mut self,
+    ) -> Result<[Self::Item; N], std::array::IntoIter<Self::Item, N>> {
+        self.log("next_chunk");
+        self.inner.next_chunk()
+    }
+
+    fn count(self) -> usize {
+        self.log("count");
+        self.inner.count()
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        self.log("last");
+        self.inner.last()
+    }
+
+    fn for_each<F: FnMut(Self::Item)>(self, f: F) {
+        self.log("for_each");
+        self.inner.for_each(f)
+    }
+
+    fn find<P: FnMut(&Self::Item) -> bool>(&mut self, predicate: P) -> Option<Self::Item> {
+        self.log("find");
+        self.inner.find(predicate)
+    }
+
+    fn position<P: FnMut(Self::Item) -> bool>(&mut self, predicate: P) -> Option<usize> {
+        self.log("position");
+        self.inner.position(predicate)
+    }
+
+    fn any<F: FnMut(Self::Item) -> bool>(&mut self, f: F) -> bool {
+        self.log("any");
+        self.inner.any(f)
+    }
+
+    fn all<F: FnMut(Self::Item) -> bool>(&mut self, f: F) -> bool {
+        self.log("all");
+        self.inner.all(f)
+    }
+
+    fn max(self) -> Option<Self::Item>
+    where
+        Self::Item: Ord,
+    {
+        self.log("max");
+        self.inner.max()
+    }
+
+    fn min(self) -> Option<Self::Item>
+    where
+        Self::Item: Ord,
+    {
+        self.log("min");
+        self.inner.min()
+    }
+
+    fn max_by_key<B: Ord, F: FnMut(&Self::Item) -> B>(self, f: F) -> Option<Self::Item> {
+        self.log("max_by_key");
+        self.inner.max_by_key(f)
+    }
+
+    fn max_by<F>(self, compare: F) -> Option<Self::Item>
+    where
+        F: FnMut(&Self::Item, &Self::Item) -> Ordering,
+    {
+        self.log("max_by");
+        self.inner.max_by(compare)
+    }
+
+    fn min_by_key<B: Ord, F: FnMut(&Self::Item) -> B>(self, f: F) -> Option<Self::Item> {
+        self.log("min_by_key");
+        self.inner.min_by_key(f)
+    }
+
+    fn min_by<F>(self, compare: F) -> Option<Self::Item>
+    where
+        F: FnMut(&Self::Item, &Self::Item) -> Ordering,
+    {
+        self.log("min_by");
+        self.inner.min_by(compare)
+    }
+
+    fn sum<S: Sum<Self::Item>>(self) -> S {
+        self.log("sum");
+        self.inner.sum()
+    }
+
+    fn product<P: Product<Self::Item>>(self) -> P {
+        self.log("product");
+        self.inner.product()
+    }
+
+    fn reduce<F>(self, f: F) -> Option<Self::Item>
+    where
+        F: FnMut(Self::Item, Self::Item) -> Self::Item,
+    {
+        self.log("reduce");
+        self.inner.reduce(f)
+    }
+
+    #[cfg(feature = "nightly")]
+    fn try_for_each<F, R>(&mut self, f: F) -> R
+    where
+        F: FnMut(Self::Item) -> R,
+        R: Try<Output = ()>,
+    {
+        self.log("try_for_each");
+        self.inner.try_for_each(f)
+    }
+
+    fn is_sorted(self) -> bool
+    where
+        Self::Item: PartialOrd,
+    {
+        self.log("is_sorted");
+        self.inner.is_sorted()
+    }
+
+    fn is_sorted_by<F>(self, compare: F) -> bool
+    where
+        F: FnMut(&Self::Item, &Self::Item) -> bool,
+    {
+        self.log("is_sorted_by");
+        self.inner.is_sorted_by(compare)
+    }
+
+    fn is_sorted_by_key<F, K>(self, f: F) -> bool
+    where
+        F: FnMut(Self::Item) -> K,
+        K: PartialOrd,
+    {
+        self.log("is_sorted_by_key");
+        self.inner.is_sorted_by_key(f)
+    }
+
+    fn cmp<J>(self, other: J) -> Ordering
+    where
+        J: IntoIterator<Item = Self::Item>,
+        Self::Item: Ord,
+    {
+        self.log("cmp");
+        self.inner.cmp(other)
+    }
+
+    fn partial_cmp<J>(self, other: J) -> Option<Ordering>
+    where
+        J: IntoIterator,
+        Self::Item: PartialOrd<J::Item>,
+    {
+        self.log("partial_cmp");
+        self.inner.partial_cmp(other)
+    }
+
+    fn eq<J>(self, other: J) -> bool
+    where
+        J: IntoIterator,
+        Self::Item: PartialEq<J::Item>,
+    {
+        self.log("eq");
+        self.inner.eq(other)
+    }
+
+    fn ne<J>(self, other: J) -> bool
+    where
+        J: IntoIterator,
+        Self::Item: PartialEq<J::Item>,
+    {
+        self.log("ne");
+        self.inner.ne(other)
+    }
+
+    fn lt<J>(self, other: J) -> bool
+    where
+        J: IntoIterator,
+        Self::Item: PartialOrd<J::Item>,
+    {
+        self.log("lt");
+        self.inner.lt(other)
+    }
+
+    fn le<J>(self, other: J) -> bool
+    where
+        J: IntoIterator,
+        Self::Item: PartialOrd<J::Item>,
+    {
+        self.log("le");
+        self.inner.le(other)
+    }
+
+    fn gt<J>(self, other: J) -> bool
+    where
+        J: IntoIterator,
+        Self::Item: PartialOrd<J::Item>,
+    {
+        self.log("gt");
+        self.inner.gt(other)
+    }
+
+    fn ge<J>(self, other: J) -> bool
+    where
+        J: IntoIterator,
+        Self::Item: PartialOrd<J::Item>,
+    {
+        self.log("ge");
+        self.inner.ge(other)
+    }
+
+    fn partition<B, F>(self, f: F) -> (B, B)
+    where
+        B: Default + Extend<Self::Item>,
+        F: FnMut(&Self::Item) -> bool,
+    {
+        self.log("partition");
+        self.inner.partition(f)
+    }
+
+    fn collect<B: FromIterator<Self::Item>>(self) -> B {
+        self.log("collect");
+        self.inner.collect()
+    }
+
+    fn find_map<B, F: FnMut(Self::Item) -> Option<B>>(&mut self, f: F) -> Option<B> {
+        self.log("find_map");
+        self.inner.find_map(f)
+    }
+
+    #[cfg(feature = "nightly")]
+    fn collect_into<E: Extend<Self::Item>>(self, collection: &mut E) -> &mut E {
+        self.log("collect_into");
+        self.inner.collect_into(collection)
+    }
+
+    #[cfg(feature = "nightly")]
+    fn is_partitioned<P: FnMut(Self::Item) -> bool>(self, predicate: P) -> bool {
+        self.log("is_partitioned");
+        self.inner.is_partitioned(predicate)
+    }
+
+    #[cfg(feature = "nightly")]
+    fn cmp_by<J, F>(self, other: J, cmp: F) -> Ordering
+    where
+        J: IntoIterator,
+        F: FnMut(Self::Item, J::Item) -> Ordering,
+    {
+        self.log("cmp_by");
+        self.inner.cmp_by(other, cmp)
+    }
+
+    #[cfg(feature = "nightly")]
+    fn partial_cmp_by<J, F>(self, other: J, partial_cmp: F) -> Option<Ordering>
+    where
+        J: IntoIterator,
+        F: FnMut(Self::Item, J::Item) -> Option<Ordering>,
+    {
+        self.log("partial_cmp_by");
+        self.inner.partial_cmp_by(other, partial_cmp)
+    }
+
+    #[cfg(feature = "nightly")]
+    fn eq_by<J, F>(self, other: J, eq: F) -> bool
+    where
+        J: IntoIterator,
+        F: FnMut(Self::Item, J::Item) -> bool,
+    {
+        self.log("eq_by");
+        self.inner.eq_by(other, eq)
+    }
+}
+
+impl<I: DoubleEndedIterator> DoubleEndedIterator for Probe<'_, I> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.log("next_back");
+        self.inner.next_back()
+    }
+
+    fn rfold<B, F: FnMut(B, Self::Item) -> B>(self, init: B, f: F) -> B {
+        self.log("rfold");
+        self.inner.rfold(init, f)
+    }
+
+    #[cfg(feature = "nightly")]
+    fn try_rfold<B, F, R>(&mut self, init: B, f: F) -> R
+    where
+        F: FnMut(B, Self::Item) -> R,
+        R: Try<Output = B>,
+    {
+        self.log("try_rfold");
+        self.inner.try_rfold(init, f)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.log("nth_back");
+        self.inner.nth_back(n)
+    }
+
+    #[cfg(feature = "nightly")]
+    fn advance_back_by(&mut self, n: usize) -> Result<(), NonZero<usize>> {
+        self.log("advance_back_by");
+        self.inner.advance_back_by(n)
+    }
+
+    fn rfind<P: FnMut(&Self::Item) -> bool>(&mut self, predicate: P) -> Option<Self::Item> {
+        self.log("rfind");
+        self.inner.rfind(predicate)
+    }
+}
+
+impl<I: ExactSizeIterator> ExactSizeIterator for Probe<'_, I> {
+    fn len(&self) -> usize {
+        self.log("len");
+        self.inner.len()
+    }
+
+    #[cfg(feature = "nightly")]
+    fn is_empty(&self) -> bool {
+        self.log("is_empty");
+        self.inner.is_empty()
+    }
+}
+
+type Wrapped<'a> = AnonIter3<Empty<u32>, Probe<'a, Range<u32>>, Empty<u32>>;
+
+/// Runs `$call` on a wrapped [`Probe`] and on a plain range, asserting that
+/// both give the same result and that the probe saw a single call to `$method`
+macro_rules! assert_forwarded {
+    ($method:literal, |$iter:ident| $call:expr) => {{
+        let expected = {
+            #[allow(unused_mut)]
+            let mut $iter = 1..10u32;
+            $call
+        };
+        let log = RefCell::new(Vec::new());
+        let actual = {
+            #[allow(unused_mut)]
+            let mut $iter: Wrapped<'_> = AnonIter3::I2(Probe {
+                inner: 1..10,
+                log: &log,
+            });
+            $call
+        };
+        assert_eq!(actual, expected, "`{}` gave a different result", $method);
+        assert_eq!(*log.borrow(), [$method], "`{}` was not forwarded", $method);
+    }};
+}
+
+#[test]
+fn iterator() {
+    assert_forwarded!("next", |it| it.next());
+    assert_forwarded!("size_hint", |it| it.size_hint());
+    assert_forwarded!("fold", |it| it.fold(0, |acc, x| acc * 3 + x));
+    assert_forwarded!("nth", |it| it.nth(4));
+    assert_forwarded!("count", |it| it.count());
+    assert_forwarded!("last", |it| it.last());
+    assert_forwarded!("for_each", |it| {
+        let mut items = Vec::new();
+        it.for_each(|x| items.push(x));
+        items
+    });
+    assert_forwarded!("find", |it| it.find(|&x| x > 4));
+    assert_forwarded!("position", |it| it.position(|x| x == 6));
+    assert_forwarded!("any", |it| it.any(|x| x == 3));
+    assert_forwarded!("all", |it| it.all(|x| x < 5));
+    assert_forwarded!("max", |it| it.max());
+    assert_forwarded!("min", |it| it.min());
+    assert_forwarded!("max_by_key", |it| it.max_by_key(|&x| x % 4));
+    assert_forwarded!("max_by", |it| it.max_by(|a, b| (a % 4).cmp(&(b % 4))));
+    assert_forwarded!("min_by_key", |it| it.min_by_key(|&x| x % 4));
+    assert_forwarded!("min_by", |it| it.min_by(|a, b| (a % 4).cmp(&(b % 4))));
+    assert_forwarded!("sum", |it| it.sum::<u32>());
+    assert_forwarded!("product", |it| it.product::<u32>());
+    assert_forwarded!("reduce", |it| it.reduce(|a, b| a * 2 + b));
+    assert_forwarded!("is_sorted", |it| it.is_sorted());
+    assert_forwarded!("is_sorted_by", |it| it.is_sorted_by(|a, b| a >= b));
+    assert_forwarded!("is_sorted_by_key", |it| it.is_sorted_by_key(|x| x % 3));
+    assert_forwarded!("cmp", |it| it.cmp(1..5));
+    assert_forwarded!("partial_cmp", |it| it.partial_cmp(1..12));
+    assert_forwarded!("eq", |it| it.eq(1..10));
+    assert_forwarded!("ne", |it| it.ne(1..10));
+    assert_forwarded!("lt", |it| it.lt(2..3));
+    assert_forwarded!("le", |it| it.le(1..10));
+    assert_forwarded!("gt", |it| it.gt(1..4));
+    assert_forwarded!("ge", |it| it.ge(0..1));
+    assert_forwarded!("partition", |it| it.partition::<Vec<_>, _>(|x| x % 3 == 0));
+    assert_forwarded!("collect", |it| it.collect::<Vec<_>>());
+    assert_forwarded!("find_map", |it| it.find_map(|x| x.checked_sub(5)));
+}
+
+#[cfg(feature = "double-ended")]
+#[test]
+fn double_ended_iterator() {
+    assert_forwarded!("next_back", |it| it.next_back());
+    assert_forwarded!("rfold", |it| it.rfold(0, |acc, x| acc * 3 + x));
+    assert_forwarded!("nth_back", |it| it.nth_back(2));
+    assert_forwarded!("rfind", |it| it.rfind(|&x| x < 4));
+}
+
+#[cfg(feature = "exact-size")]
+#[test]
+fn exact_size_iterator() {
+    assert_forwarded!("len", |it| it.len());
+}
+
+#[cfg(feature = "nightly")]
+#[test]
+fn nightly() {
+    assert_forwarded!("try_fold", |it| it
+        .try_fold(0u32, |acc, x| acc.checked_add(x)));
+    #[cfg(feature = "double-ended")]
+    assert_forwarded!("try_rfold", |it| it
+        .try_rfold(0u32, |acc, x| acc.checked_sub(x)));
+    assert_forwarded!("try_for_each", |it| it.try_for_each(|x| if x < 4 {
+        Ok(())
+    } else {
+        Err(x)
+    }));
+    assert_forwarded!("collect_into", |it| it
+        .collect_into(&mut Vec::new())
+        .clone());
+    assert_forwarded!("is_partitioned", |it| it.is_partitioned(|x| x < 4));
+    assert_forwarded!("cmp_by", |it| it.cmp_by(1..10, |a, b| b.cmp(&a)));
+    assert_forwarded!("partial_cmp_by", |it| it
+        .partial_cmp_by(1..10, |a, b| b.partial_cmp(&a)));
+    assert_forwarded!("eq_by", |it| it.eq_by(1..10, |a, b| a == b));
+    assert_forwarded!("advance_by", |it| it.advance_by(12));
+    #[cfg(feature = "double-ended")]
+    assert_forwarded!("advance_back_by", |it| it.advance_back_by(3));
+    #[cfg(feature = "exact-size")]
+    assert_forwarded!("is_empty", |it| it.is_empty());
+    assert_forwarded!("next_chunk", |it| it.next_chunk::<4>().ok());
+    assert_forwarded!("next_chunk", |it| it
+        .next_chunk::<12>()
+        .map_err(Iterator::collect::<Vec<_>>));
+}
@@ -0,0 +1,375 @@
+//! Checks the `anon!` macro, which wraps the arms of a `match` in variants, and `define_anon_iter!`.
+
+use anon_iter::anon;
+
+#[cfg(feature = "arity-4")]
+fn branch(x: u32) -> impl Iterator<Item = u32> {
+    anon!(match x % 4 {
+        0 => 1..4,
+        1 | 2 if x > 10 => vec![7, 8].into_iter(),
+        1 | 2 => {
+            let start = x * 10;
+            start..start + 2
+        }
+        _ => std::iter::once(x),
+    })
+}
+
+#[cfg(feature = "arity-4")]
+#[test]
+fn wraps_each_arm() {
+    assert_eq!(branch(0).collect::<Vec<_>>(), [1, 2, 3]);
+    assert_eq!(branch(13).collect::<Vec<_>>(), [7, 8]);
+    assert_eq!(branch(1).collect::<Vec<_>>(), [10, 11]);
+    assert_eq!(branch(3).collect::<Vec<_>>(), [3]);
+}
+
+#[test]
+fn picks_the_arity() {
+    let it = anon!(match 2 {
+        _ => std::iter::once(1),
+    });
+    let it: anon_iter::AnonIter1<_> = it;
+    assert_eq!(it.into_inner().sum::<u32>(), 1);
+
+    let it = anon!(match 2 {
+        0 => std::iter::empty::<u32>(),
+        _ => std::iter::once(1),
+    });
+    let it: anon_iter::AnonIter2<_, _> = it;
+    assert!(it.is_i2());
+
+    #[cfg(feature = "arity-12")]
+    {
+        let it = anon!(match 12 {
+            1 => 0..1,
+            2 => 0..2,
+            3 => 0..3,
+            4 => 0..4,
+            5 => 0..5,
+            6 => 0..6,
+            7 => 0..7,
+            8 => 0..8,
+            9 => 0..9,
+            10 => 0..10,
+            11 => 0..11,
+            _ => 0..12,
+        });
+        assert_eq!(it.variant_index(), 12);
+        assert_eq!(it.count(), 12);
+    }
+}
+
+anon_iter::define_anon_iter! {
+    #[derive(Clone, Debug)]
+    enum Forwards<A, B, C, D, E, F, G, H, I, J, K, L, M>: Iterator, FusedIterator;
+}
+
+type Wide = Forwards<
+    std::ops::Range<u32>,
+    std::iter::Empty<u32>,
+    std::iter::Empty<u32>,
+    std::iter::Empty<u32>,
+    std::iter::Empty<u32>,
+    std::iter::Empty<u32>,
+    std::iter::Empty<u32>,
+    std::iter::Empty<u32>,
+    std::iter::Empty<u32>,
+    std::iter::Empty<u32>,
+    std::iter::Empty<u32>,
+    std::iter::Empty<u32>,
+    std::vec::IntoIter<u32>,
+>;
+
+#[test]
+fn defines_custom_enums() {
+    let it: Wide = Forwards::A(1..4);
+    assert_eq!(it.clone().sum::<u32>(), 6);
+    assert_eq!(it.size_hint(), (3, Some(3)));
+
+    let it: Wide = Forwards::M(vec![5, 6].into_iter());
+    assert_eq!(it.collect::<Vec<_>>(), [5, 6]);
+}
+
+#[cfg(feature = "macros")]
+mod attribute {
+    use anon_iter::macros::anon;
+
+    /// Returns from 5 places, so it needs `AnonIter5`
+    #[cfg(feature = "arity-8")]
+    #[anon]
+    fn returns(x: u32) -> impl Iterator<Item = u32> {
+        if x == 0 {
+            return std::iter::empty();
+        }
+        let squares = move || (1..=x).map(|y| y * y);
+        match x % 3 {
+            0 => squares().collect::<Vec<_>>().into_iter(),
+            1 if x > 10 => unreachable!(),
+            1 => {
+                let start = x * 10;
+                start..start + 2
+            }
+            _ => {
+                if x > 100 {
+                    std::iter::once(x).chain(None)
+                } else {
+                    std::iter::once(x).chain(Some(x))
+                }
+            }
+        }
+    }
+
+    #[anon]
+    fn single() -> impl Iterator<Item = u32> {
+        1..3
+    }
+
+    #[test]
+    fn wraps_each_return_point() {
+        #[cfg(feature = "arity-8")]
+        {
+            assert_eq!(returns(0).collect::<Vec<_>>(), []);
+            assert_eq!(returns(3).collect::<Vec<_>>(), [1, 4, 9]);
+            assert_eq!(returns(1).collect::<Vec<_>>(), [10, 11]);
+            assert_eq!(returns(2).collect::<Vec<_>>(), [2, 2]);
+            assert_eq!(returns(200).collect::<Vec<_>>(), [200]);
+        }
+        assert_eq!(single().collect::<Vec<_>>(), [1, 2]);
+    }
+
+    /// 13 values go in `AnonIter16`, with `Empty` in the 3 variants left over
+    #[cfg(feature = "high-arity")]
+    #[anon]
+    fn thirteen(x: u32) -> impl Iterator<Item = u32> {
+        match x {
+            1 => 0..1,
+            2 => 0..2,
+            3 => 0..3,
+            4 => 0..4,
+            5 => 0..5,
+            6 => 0..6,
+            7 => 0..7,
+            8 => 0..8,
+            9 => 0..9,
+            10 => 0..10,
+            11 => 0..11,
+            12 => 0..12,
+            _ => 0..13,
+        }
+    }
+
+    #[cfg(feature = "high-arity")]
+    #[test]
+    fn pads_the_arity() {
+        assert_eq!(thirteen(4).count(), 4);
+        assert_eq!(thirteen(40).count(), 13);
+    }
+}
+
+#[cfg(feature = "macros")]
+mod derive {
+    use anon_iter::macros::IteratorEnum;
+    use std::iter::{Empty, Rev};
+    use std::ops::Range;
+
+    #[derive(IteratorEnum)]
+    enum Rows<'a, I: Clone = Empty<u32>>
+    where
+        I: Iterator<Item = u32>,
+    {
+        Cached(std::iter::Copied<std::slice::Iter<'a, u32>>),
+        Streamed(I),
+        Backwards(Rev<Range<u32>>),
+    }
+
+    /// Has an inherent method with the same name as an `Iterator` method
+    #[derive(Clone)]
+    struct Odd(Range<u32>);
+
+    impl Odd {
+        #[allow(dead_code)]
+        fn count(&self) -> &'static str {
+            "not the iterator's"
+        }
+    }
+
+    impl Iterator for Odd {
+        type Item = u32;
+
+        fn next(&mut self) -> Option<u32> {
+            self.0.by_ref().find(|x| x % 2 == 1)
+        }
+    }
+
+    #[test]
+    fn forwards_iteration() {
+        let cache = [4, 5, 6];
+        let rows: Rows<'_> = Rows::Cached(cache.iter().copied());
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows.rev().collect::<Vec<_>>(), [6, 5, 4]);
+
+        let rows: Rows<'_> = Rows::Backwards((0..3).rev());
+        assert_eq!(rows.collect::<Vec<_>>(), [2, 1, 0]);
+
+        let rows: Rows<'_, Odd> = Rows::Streamed(Odd(0..6));
+        assert_eq!(rows.count(), 3);
+    }
+
+    #[test]
+    fn implements_traits_when_every_variant_does() {
+        fn double_ended<I: DoubleEndedIterator>() {}
+        fn fused<I: std::iter::FusedIterator>() {}
+        double_ended::<Rows<'_>>();
+        fused::<Rows<'_>>();
+    }
+}
+
+#[cfg(feature = "macros")]
+mod forward {
+    use anon_iter::macros::anon;
+    use std::iter::Once;
+    use std::ops::Range;
+
+    #[anon(Iterator, DoubleEndedIterator)]
+    enum Numbers {
+        Many(Range<u32>),
+        One(Once<u32>),
+    }
+
+    /// Forwarding only formatting traits needs no iterators
+    #[anon(Display, LowerHex)]
+    enum Label {
+        Number(u32),
+        Byte(u8),
+    }
+
+    #[test]
+    fn forwards_the_listed_traits() {
+        assert_eq!(Numbers::Many(1..4).rev().collect::<Vec<_>>(), [3, 2, 1]);
+        assert_eq!(Numbers::One(std::iter::once(7)).next(), Some(7));
+
+        assert_eq!(Label::Number(255).to_string(), "255");
+        assert_eq!(format!("{:x}", Label::Number(255)), "ff");
+        assert_eq!(format!("{:x}", Label::Byte(10)), "a");
+    }
+
+    /// Bytes that can be iterated, read and printed
+    struct Chunk(&'static [u8]);
+
+    impl Iterator for Chunk {
+        type Item = u8;
+
+        fn next(&mut self) -> Option<u8> {
+            let (first, rest) = self.0.split_first()?;
+            self.0 = rest;
+            Some(*first)
+        }
+    }
+
+    impl std::io::Read for Chunk {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.0.read(buf)
+        }
+    }
+
+    impl std::fmt::Display for Chunk {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{} bytes", self.0.len())
+        }
+    }
+
+    #[anon(Iterator, Read, Display)]
+    enum Input {
+        Inline(Chunk),
+        Cached(Chunk),
+    }
+
+    #[derive(Debug)]
+    #[anon(Future, Error, Display)]
+    enum Outcome<A, B> {
+        Now(A),
+        Later(B),
+    }
+
+    #[test]
+    fn forwards_the_other_families() {
+        use std::future::Future;
+        use std::io::Read;
+        use std::sync::Arc;
+        use std::task::{Context, Poll, Wake};
+
+        let mut input = Input::Cached(Chunk(b"abc"));
+        assert_eq!(input.to_string(), "3 bytes");
+        assert_eq!(input.next(), Some(b'a'));
+        let mut rest = String::new();
+        input.read_to_string(&mut rest).unwrap();
+        assert_eq!(rest, "bc");
+        assert_eq!(Input::Inline(Chunk(b"xy")).count(), 2);
+
+        struct Noop;
+        impl Wake for Noop {
+            fn wake(self: Arc<Self>) {}
+        }
+        let waker = Arc::new(Noop).into();
+        let mut cx = Context::from_waker(&waker);
+        // An `async` block isn't `Unpin`, so polling it goes through the pin projection
+        let mut later = Box::pin(Outcome::<std::future::Ready<u32>, _>::Later(async { 7 }));
+        assert_eq!(later.as_mut().poll(&mut cx), Poll::Ready(7));
+        let mut now = Outcome::<_, std::future::Ready<u32>>::Now(std::future::ready(1));
+        assert_eq!(std::pin::Pin::new(&mut now).poll(&mut cx), Poll::Ready(1));
+
+        let error: Outcome<std::fmt::Error, std::num::ParseIntError> =
+            Outcome::Later("x".parse::<u32>().unwrap_err());
+        let error: &dyn std::error::Error = &error;
+        assert!(error.source().is_none());
+    }
+}
+
+#[cfg(feature = "macros")]
+mod match_iters {
+    use anon_iter::macros::match_iters;
+
+    fn many(x: u32) -> impl ExactSizeIterator<Item = u32> {
+        match_iters!(match x {
+            0 => 0..0,
+            1 => 0..1,
+            2 => 0..2,
+            3 => 0..3,
+            4 => 0..4,
+            5 => 0..5,
+            6 => 0..6,
+            7 => 0..7,
+            8 => 0..8,
+            9 => 0..9,
+            10 => 0..10,
+            11 => 0..11,
+            12 => 0..12,
+            13 => std::iter::once(13),
+            u32::MAX => panic!("too many"),
+            _ => {
+                let items = vec![x; 2];
+                items.into_iter()
+            }
+        })
+    }
+
+    #[test]
+    fn defines_an_enum_per_match() {
+        assert_eq!(many(12).len(), 12);
+        assert_eq!(many(13).collect::<Vec<_>>(), [13]);
+        assert_eq!(many(20).collect::<Vec<_>>(), [20, 20]);
+    }
+
+    #[test]
+    fn nests() {
+        let it = match_iters!(match 2 {
+            0 => std::iter::empty(),
+            x => match_iters!(match x {
+                1 => std::iter::once(1),
+                _ => vec![x, x].into_iter(),
+            }),
+        });
+        assert_eq!(it.sum::<u32>(), 4);
+    }
+}
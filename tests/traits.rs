@@ -0,0 +1,427 @@
+//! Checks the impls of traits that aren't about iteration itself.
+
+use anon_iter::AnonIter3;
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::{once, Empty, Once};
+use std::ops::Range;
+
+type Wrapped = AnonIter3<Empty<u32>, Range<u32>, Once<u32>>;
+
+/// Counts down to 1. Unlike the iterators in `std`, it is `Copy`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct Countdown(u32);
+
+impl Iterator for Countdown {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        let n = self.0;
+        self.0 = n.checked_sub(1)?;
+        Some(n)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Countdown {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Countdown(u32::from(u8::arbitrary(u)?)))
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+impl quickcheck::Arbitrary for Countdown {
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        Countdown(u32::arbitrary(g))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        Box::new(self.0.shrink().map(Countdown))
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Countdown {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(f, "Countdown({=u32})", self.0)
+    }
+}
+
+impl fmt::Display for Countdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}...", self.0)
+    }
+}
+
+impl fmt::LowerHex for Countdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.0, f)
+    }
+}
+
+type Countdowns = AnonIter3<Countdown, Countdown, Countdown>;
+
+#[test]
+fn clone() {
+    let mut it: Wrapped = AnonIter3::I2(1..5);
+    it.next();
+    let copy = it.clone();
+    assert_eq!(it.collect::<Vec<_>>(), [2, 3, 4]);
+    assert_eq!(copy.collect::<Vec<_>>(), [2, 3, 4]);
+
+    let it: Wrapped = AnonIter3::I3(once(7));
+    assert_eq!(it.clone().chain(it).collect::<Vec<_>>(), [7, 7]);
+}
+
+#[test]
+fn copy() {
+    let it: Countdowns = AnonIter3::I2(Countdown(3));
+    let copy = it;
+    assert_eq!(it.collect::<Vec<_>>(), [3, 2, 1]);
+    assert_eq!(copy.collect::<Vec<_>>(), [3, 2, 1]);
+}
+
+#[test]
+fn debug() {
+    /// What `#[derive(Debug)]` would print
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    enum Derived {
+        I3(Once<u32>),
+    }
+
+    let it: Wrapped = AnonIter3::I2(1..5);
+    assert_eq!(format!("{:?}", it), "I2(1..5)");
+    let it: Wrapped = AnonIter3::I3(once(7));
+    assert_eq!(format!("{:#?}", it), format!("{:#?}", Derived::I3(once(7))));
+}
+
+#[cfg(feature = "fmt")]
+#[test]
+fn display() {
+    let it: Countdowns = AnonIter3::I2(Countdown(3));
+    assert_eq!(it.to_string(), "3...");
+    assert_eq!(format!("{:#06x}", Countdowns::I3(Countdown(255))), "0x00ff");
+}
+
+#[test]
+fn eq() {
+    let it: Countdowns = AnonIter3::I2(Countdown(3));
+    assert_eq!(it, AnonIter3::I2(Countdown(3)));
+    assert_ne!(it, AnonIter3::I2(Countdown(4)));
+    assert_ne!(it, AnonIter3::I3(Countdown(3)));
+}
+
+#[test]
+fn hash() {
+    fn hash(value: impl Hash) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let it: Countdowns = AnonIter3::I2(Countdown(3));
+    assert_eq!(hash(it), hash(Countdowns::I2(Countdown(3))));
+    assert_ne!(hash(it), hash(Countdowns::I2(Countdown(4))));
+    assert_ne!(hash(it), hash(Countdowns::I3(Countdown(3))));
+}
+
+#[test]
+fn ord() {
+    let mut its: Vec<Countdowns> = vec![
+        AnonIter3::I3(Countdown(1)),
+        AnonIter3::I1(Countdown(5)),
+        AnonIter3::I2(Countdown(2)),
+        AnonIter3::I1(Countdown(4)),
+    ];
+    its.sort();
+    assert_eq!(
+        its,
+        [
+            AnonIter3::I1(Countdown(4)),
+            AnonIter3::I1(Countdown(5)),
+            AnonIter3::I2(Countdown(2)),
+            AnonIter3::I3(Countdown(1)),
+        ]
+    );
+    assert!(Countdowns::I2(Countdown(0)) > Countdowns::I1(Countdown(9)));
+    // `.partial_cmp()` would call `Iterator::partial_cmp`, which compares the items
+    assert_eq!(
+        PartialOrd::partial_cmp(&Countdowns::I2(Countdown(3)), &AnonIter3::I2(Countdown(3))),
+        Some(Ordering::Equal)
+    );
+}
+
+#[test]
+fn default() {
+    let it: Wrapped = Default::default();
+    assert_eq!(it.count(), 0);
+    let mut it = AnonIter3::<Range<u32>, Empty<u32>, Once<u32>>::I1(1..5);
+    assert_eq!(std::mem::take(&mut it).count(), 4);
+    assert!(matches!(it, AnonIter3::I1(Range { start: 0, end: 0 })));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde() {
+    type Ranges = AnonIter3<Range<u32>, Range<u32>, Range<u32>>;
+
+    let it: Ranges = AnonIter3::I2(1..5);
+    let json = serde_json::to_string(&it).unwrap();
+    assert_eq!(json, r#"{"variant":"I2","iter":{"start":1,"end":5}}"#);
+    let it: Ranges =
+        serde_json::from_str(r#"{"iter":{"start":1,"end":5},"variant":"I3"}"#).unwrap();
+    assert!(matches!(it, AnonIter3::I3(Range { start: 1, end: 5 })));
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn arbitrary() {
+    use arbitrary::{Arbitrary, Unstructured};
+    let mut seen = [false; 3];
+    for byte in 0..=u8::MAX {
+        let data = [byte, 7];
+        match Countdowns::arbitrary(&mut Unstructured::new(&data)).unwrap() {
+            AnonIter3::I1(Countdown(n)) => {
+                seen[0] = true;
+                assert_eq!(n, 7);
+            }
+            AnonIter3::I2(Countdown(n)) => {
+                seen[1] = true;
+                assert_eq!(n, 7);
+            }
+            AnonIter3::I3(Countdown(n)) => {
+                seen[2] = true;
+                assert_eq!(n, 7);
+            }
+        }
+    }
+    assert_eq!(seen, [true; 3]);
+}
+
+#[cfg(feature = "quickcheck")]
+#[test]
+fn quickcheck() {
+    use quickcheck::{Arbitrary, Gen};
+
+    let mut g = Gen::new(10);
+    let mut seen = [false; 3];
+    for _ in 0..100 {
+        let it = Countdowns::arbitrary(&mut g);
+        seen[match it {
+            AnonIter3::I1(_) => 0,
+            AnonIter3::I2(_) => 1,
+            AnonIter3::I3(_) => 2,
+        }] = true;
+    }
+    assert_eq!(seen, [true; 3]);
+
+    let shrunk: Vec<_> = Countdowns::I2(Countdown(8)).shrink().collect();
+    let expected: Vec<_> = Countdown(8).shrink().map(AnonIter3::I2).collect();
+    assert!(!shrunk.is_empty());
+    assert_eq!(shrunk, expected);
+}
+
+/// Formatting needs a `defmt` logger, which only exists on embedded targets
+#[cfg(feature = "defmt")]
+#[test]
+fn defmt() {
+    fn is_format<T: defmt::Format>() {}
+    is_format::<Countdowns>();
+}
+
+#[cfg(feature = "arity-8")]
+#[test]
+fn widen() {
+    use anon_iter::{AnonIter2, AnonIter5};
+
+    type E = Empty<u32>;
+
+    let it: AnonIter2<Range<u32>, Once<u32>> = AnonIter2::I2(once(7));
+    let it: AnonIter3<Range<u32>, Once<u32>, E> = it.into();
+    assert!(it.is_i2());
+    let it: AnonIter5<Range<u32>, Once<u32>, E, E, E> = it.into();
+    assert_eq!(it.unwrap_i2().collect::<Vec<_>>(), [7]);
+    let it: AnonIter5<Range<u32>, Once<u32>, E, E, E> = AnonIter2::I1(1..5).into();
+    assert_eq!(it.unwrap_i1(), 1..5);
+}
+
+#[cfg(feature = "arity-8")]
+#[test]
+fn narrow() {
+    use anon_iter::{AnonIter2, AnonIter5};
+    use std::convert::TryFrom;
+
+    type E = Empty<u32>;
+
+    let it: AnonIter5<Range<u32>, Once<u32>, E, E, E> = AnonIter5::I2(once(7));
+    let it: AnonIter3<Range<u32>, Once<u32>, E> = it.try_narrow().unwrap();
+    let it = AnonIter2::<Range<u32>, Once<u32>>::try_from(it).unwrap();
+    assert_eq!(it.unwrap_i2().collect::<Vec<_>>(), [7]);
+
+    let it: AnonIter5<Range<u32>, Once<u32>, E, E, E> = AnonIter5::I4(std::iter::empty());
+    let it = it.try_narrow::<AnonIter3<_, _, _>>().unwrap_err();
+    assert!(it.is_i4());
+}
+
+#[cfg(feature = "either")]
+#[test]
+fn either() {
+    use anon_iter::AnonIter2;
+    use either::Either;
+
+    let it: AnonIter2<Range<u32>, Once<u32>> = Either::Left(1..5).into();
+    assert_eq!(it.as_i1(), Some(&(1..5)));
+    let it: AnonIter2<Range<u32>, Once<u32>> = Either::Right(once(7)).into();
+    assert!(it.is_i2());
+    let it: Either<Range<u32>, Once<u32>> = AnonIter2::I1(1..5).into();
+    assert_eq!(it.left(), Some(1..5));
+    let it: Either<Range<u32>, Once<u32>> = AnonIter2::I2(once(7)).into();
+    assert!(it.is_right());
+}
+
+#[cfg(feature = "either")]
+#[test]
+fn from_nested_either() {
+    use anon_iter::AnonIter3;
+    use either::Either::{Left, Right};
+
+    type Nested3 = either::Either<Range<u32>, either::Either<Once<u32>, Empty<u32>>>;
+    let cases: [(Nested3, usize); 3] = [
+        (Left(1..5), 1),
+        (Right(Left(once(7))), 2),
+        (Right(Right(std::iter::empty())), 3),
+    ];
+    for (nested, index) in cases {
+        assert_eq!(AnonIter3::from_nested(nested).variant_index(), index);
+    }
+    #[cfg(feature = "arity-4")]
+    {
+        use anon_iter::AnonIter4;
+
+        let it = AnonIter4::<Empty<u32>, Empty<u32>, Range<u32>, Once<u32>>::from_nested(Right(
+            Right(Left(1..5)),
+        ));
+        assert_eq!(it.as_i3(), Some(&(1..5)));
+        let it = AnonIter4::<Empty<u32>, Empty<u32>, Range<u32>, Once<u32>>::from_nested(Right(
+            Right(Right(once(7))),
+        ));
+        assert_eq!(it.collect::<Vec<_>>(), [7]);
+    }
+}
+
+#[cfg(feature = "futures")]
+#[test]
+fn futures_either() {
+    use anon_iter::AnonIter2;
+    use futures::future::Either;
+
+    let it: AnonIter2<Range<u32>, Once<u32>> = Either::Left(1..5).into();
+    assert_eq!(it.as_i1(), Some(&(1..5)));
+    let it: AnonIter2<Range<u32>, Once<u32>> = Either::Right(once(7)).into();
+    assert!(it.is_i2());
+    let it: Either<Range<u32>, Once<u32>> = AnonIter2::I1(1..5).into();
+    assert!(matches!(it, Either::Left(Range { start: 1, end: 5 })));
+    let it: Either<Range<u32>, Once<u32>> = AnonIter2::I2(once(7)).into();
+    assert!(matches!(it, Either::Right(_)));
+}
+
+#[test]
+fn into_iterator_for_references() {
+    use anon_iter::Anon2;
+
+    let mut anon: Anon2<Vec<u32>, [u32; 2]> = Anon2::I1(vec![1, 2, 3]);
+    let mut borrowed = Vec::new();
+    for x in &anon {
+        borrowed.push(*x);
+    }
+    assert_eq!(borrowed, [1, 2, 3]);
+
+    for x in anon.as_mut().into_iters() {
+        *x *= 10;
+    }
+    assert_eq!(anon, Anon2::I1(vec![10, 20, 30]));
+
+    let anon: Anon2<Vec<u32>, [u32; 2]> = Anon2::I2([4, 5]);
+    assert_eq!((&anon).into_iter().sum::<u32>(), 9);
+}
+
+#[test]
+fn anon_chain() {
+    use anon_iter::AnonChain;
+    use std::iter::Rev;
+    use std::vec::IntoIter;
+
+    type Chain =
+        AnonChain<Range<u32>, AnonChain<Once<u32>, AnonChain<IntoIter<u32>, Rev<Range<u32>>>>>;
+
+    let it: Chain = AnonChain::inject(once(4));
+    assert!(matches!(it, AnonChain::Tail(AnonChain::Head(_))));
+    #[cfg(all(feature = "double-ended", feature = "exact-size"))]
+    {
+        let it: Chain = AnonChain::inject((0..3).rev());
+        assert_eq!(it.len(), 3);
+        assert_eq!(it.rev().collect::<Vec<_>>(), [0, 1, 2]);
+    }
+
+    #[cfg(feature = "arity-4")]
+    {
+        let it: Chain = anon_iter::AnonIter4::I3(vec![5, 6].into_iter()).into();
+        assert_eq!(it.collect::<Vec<_>>(), [5, 6]);
+    }
+
+    let it: AnonChain<Range<u32>, Empty<u32>> = AnonChain::Head(1..4);
+    assert_eq!(it.sum::<u32>(), 6);
+    #[cfg(feature = "fmt")]
+    {
+        let label: AnonChain<u8, &str> = AnonChain::Tail("ten");
+        assert_eq!(label.to_string(), "ten");
+    }
+}
+
+#[cfg(feature = "fmt")]
+#[test]
+fn non_iterator_traits() {
+    use anon_iter::Anon2;
+    use std::future::{ready, Future, Ready};
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+
+    let label: Anon2<u8, &str> = Anon2::I2("ten");
+    assert_eq!(label.to_string(), "ten");
+
+    let error: Anon2<fmt::Error, std::num::ParseIntError> =
+        Anon2::I2("x".parse::<u8>().unwrap_err());
+    let error: &dyn std::error::Error = &error;
+    assert_eq!(error.to_string(), "invalid digit found in string");
+
+    struct Noop;
+    impl Wake for Noop {
+        fn wake(self: Arc<Self>) {}
+    }
+    let waker = Waker::from(Arc::new(Noop));
+    let mut future: Anon2<Ready<u32>, std::future::Pending<u32>> = Anon2::I1(ready(7));
+    let poll = std::pin::Pin::new(&mut future).poll(&mut Context::from_waker(&waker));
+    assert_eq!(poll, Poll::Ready(7));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn io() {
+    use anon_iter::Anon2;
+    use std::io::{BufRead, Cursor, Read, Write};
+
+    let mut reader: Anon2<&[u8], Cursor<Vec<u8>>> = Anon2::I2(Cursor::new(b"line\nrest".to_vec()));
+    let mut line = String::new();
+    reader.read_line(&mut line).unwrap();
+    assert_eq!(line, "line\n");
+    let mut rest = String::new();
+    reader.read_to_string(&mut rest).unwrap();
+    assert_eq!(rest, "rest");
+
+    let mut writer: Anon2<Vec<u8>, std::io::Sink> = Anon2::I1(Vec::new());
+    write!(writer, "{}", 42).unwrap();
+    assert_eq!(writer.unwrap_i1(), b"42");
+}